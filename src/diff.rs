@@ -0,0 +1,69 @@
+use std::cmp::min;
+
+// Lines of unchanged source shown around a change
+const CONTEXT: usize = 3;
+
+// Mutations change one region of the program, so a single hunk spanning everything between the common prefix and suffix is enough
+fn common_ends(original: &[&str], mutated: &[&str]) -> (usize, usize) {
+    let prefix = original.iter().zip(mutated.iter()).take_while(|(a, b)| a == b).count();
+    let remaining = min(original.len(), mutated.len()) - prefix;
+    let suffix = original.iter().rev().zip(mutated.iter().rev()).take(remaining).take_while(|(a, b)| a == b).count();
+
+    (prefix, suffix)
+}
+
+// The lines of the mutated source which differ from the original, or the removed lines marked with `- ` if there are none
+pub fn changed_lines(original: &str, mutated: &str) -> String {
+    let original: Vec<&str> = original.lines().collect();
+    let mutated: Vec<&str> = mutated.lines().collect();
+    let (prefix, suffix) = common_ends(&original, &mutated);
+
+    if prefix + suffix == mutated.len() {
+        let removed: Vec<String> = original[prefix..original.len() - suffix].iter().map(|line| format!("- {}", line.trim_start())).collect();
+        return removed.join("\n");
+    }
+
+    mutated[prefix..mutated.len() - suffix].join("\n")
+}
+
+fn hunk_range(start: usize, count: usize) -> String {
+    // An empty range refers to the line before it
+    match count {
+        0 => format!("{},0", start),
+        _ => format!("{},{}", start + 1, count),
+    }
+}
+
+// Both sources are printed by pymut, so hunk ranges count lines of the printed program. The line of the mutated node in
+// the real file is given after the hunk range.
+pub fn unified_diff(original: &str, mutated: &str, path: &str, node_line: usize) -> String {
+    let original: Vec<&str> = original.lines().collect();
+    let mutated: Vec<&str> = mutated.lines().collect();
+    let (prefix, suffix) = common_ends(&original, &mutated);
+
+    if prefix == original.len() && prefix == mutated.len() {
+        return String::new();
+    }
+
+    let start = prefix.saturating_sub(CONTEXT);
+    let original_end = min(original.len(), original.len() - suffix + CONTEXT);
+    let mutated_end = min(mutated.len(), mutated.len() - suffix + CONTEXT);
+
+    let mut diff = format!("--- {} (printed by pymut)\n+++ {} (mutated, printed by pymut)\n", path, path);
+    diff.push_str(&format!("@@ -{} +{} @@ {}:{}\n", hunk_range(start, original_end - start), hunk_range(start, mutated_end - start), path, node_line));
+
+    for line in &original[start..prefix] {
+        diff.push_str(&format!(" {}\n", line));
+    }
+    for line in &original[prefix..original.len() - suffix] {
+        diff.push_str(&format!("-{}\n", line));
+    }
+    for line in &mutated[prefix..mutated.len() - suffix] {
+        diff.push_str(&format!("+{}\n", line));
+    }
+    for line in &original[original.len() - suffix..original_end] {
+        diff.push_str(&format!(" {}\n", line));
+    }
+
+    diff
+}
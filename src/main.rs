@@ -2,7 +2,7 @@ use std::fs;
 use std::process;
 use std::path::PathBuf;
 use sha1::{Sha1, Digest};
-use nix::unistd::{fork, ForkResult};
+use nix::unistd::{fork, pipe, read, write, close, ForkResult};
 use nix::sys::wait::{waitpid, WaitPidFlag, WaitStatus};
 use nix::sys::signal::{kill, Signal};
 use std::time::Duration;
@@ -21,7 +21,13 @@ use rustpython_vm::{
 mod traversal;
 mod mutation;
 mod serde_compatibility;
-use crate::mutation::{Mutation, explore_mutations, apply_mutation};
+mod scope;
+mod unparse;
+mod diff;
+mod pragma;
+use crate::mutation::{Mutation, NodeLocation, explore_mutations, apply_mutation};
+use crate::unparse::unparse_program;
+use crate::diff::{changed_lines, unified_diff};
 
 extern crate hex;
 
@@ -41,8 +47,9 @@ struct CommandLineOptions {
     #[clap(short = "m", long = "mode")]
     mode: Mode,
 
+    // Not needed in unparse mode
     #[clap(short = "d", long = "database")]
-    database: String,
+    database: Option<String>,
 
     #[clap(short = "f", long = "file")]
     file: String,
@@ -52,6 +59,7 @@ struct CommandLineOptions {
 enum Mode {
     Explore,
     Execute,
+    Unparse,
 }
 
 #[derive(Display)]
@@ -60,20 +68,27 @@ enum RunResult {
     //CompileError, - reported as runtime error
     RuntimeError,
     Timeout,
+    NotFound,
 }
 
 mod schema {
     table! {
-        mutations (file_sha1, location, mutation) {
+        mutations (file_sha1, line, column, node, mutation) {
             file_sha1 -> Text,
-            location -> Integer,
+            line -> Integer,
+            column -> Integer,
+            node -> Text,
             mutation -> Text,
+            source -> Text,
+            diff -> Text,
         }
     }
     table! {
-        results (file_sha1, location, mutation, test_runner_sha1, result) {
+        results (file_sha1, line, column, node, mutation, test_runner_sha1, result) {
             file_sha1 -> Text,
-            location -> Integer,
+            line -> Integer,
+            column -> Integer,
+            node -> Text,
             mutation -> Text,
             test_runner_sha1 -> Text,
             result -> Text,
@@ -88,34 +103,64 @@ use schema::results;
 #[table_name = "mutations"]
 struct MutationEntry {
     file_sha1: String,
-    location: i32,
+    line: i32,
+    column: i32,
+    node: String,
     mutation: String,
+    source: String,
+    diff: String,
 }
 
 #[derive(Clone, Insertable, Queryable, PartialEq)]
 #[table_name = "results"]
 struct ResultEntry {
     file_sha1: String,
-    location: i32,
+    line: i32,
+    column: i32,
+    node: String,
     mutation: String,
     test_runner_sha1: String,
     result: String,
 }
 
+// Tables created by older versions of pymut have other columns, so every insert and load would fail
+fn check_columns(conn: &SqliteConnection, database: &str, table: &str, columns: &str) {
+    if conn.execute(&format!("select {} from {} limit 0", columns, table)).is_err() {
+        eprintln!("The {} table in {} is missing or was created by an older version of pymut, recreate the database by exploring again", table, database);
+        process::exit(1);
+    }
+}
+
+fn required_database(command_line_options: &CommandLineOptions) -> String {
+    match &database {
+        Some(database) => database.clone(),
+        None => {
+            eprintln!("A database is required in {} mode, pass one with -d", command_line_options.mode);
+            process::exit(1);
+        },
+    }
+}
+
 fn execute(command_line_options: CommandLineOptions) {
-    let conn = SqliteConnection::establish(&command_line_options.database).unwrap();
+    let database = required_database(&command_line_options);
+    let conn = SqliteConnection::establish(&database).unwrap();
 
     conn.execute(
         "create table if not exists results (
             file_sha1 text,
-            location integer,
+            line integer,
+            column integer,
+            node text,
             mutation text,
             test_runner_sha1 text,
             result text,
-            primary key (file_sha1, location, mutation, test_runner_sha1, result)
+            primary key (file_sha1, line, column, node, mutation, test_runner_sha1, result)
         )"
     ).unwrap();
 
+    check_columns(&conn, &database, "mutations", "file_sha1, line, column, node, mutation, source, diff");
+    check_columns(&conn, &database, "results", "file_sha1, line, column, node, mutation, test_runner_sha1, result");
+
     let file = fs::read_to_string(&command_line_options.file).expect("");
     let test_runner_hash = hex::encode(Sha1::digest(file.as_bytes()).as_slice());
 
@@ -130,6 +175,9 @@ fn execute(command_line_options: CommandLineOptions) {
     for mutation_entry in mutation_entries {
         let mutation_entry_copy = mutation_entry.clone();
 
+        // The child writes to the pipe once the mutation is applied, which no exit status of the tests could fake
+        let (applied_reader, applied_writer) = pipe().unwrap();
+
         let callback = Box::new(move |ast: ast::Program, src: &str| -> ast::Program {
             let target_file_hash = mutation_entry.file_sha1.clone();
             let file_hash = hex::encode(Sha1::digest(src.as_bytes()).as_slice());
@@ -137,8 +185,13 @@ fn execute(command_line_options: CommandLineOptions) {
             if file_hash == target_file_hash {
                 let mut mutated_ast = ast.clone();
                 let mutation_type = serde_json::from_str(&mutation_entry.mutation).unwrap();
-                let loaded_mutation = Mutation{traversal_location: mutation_entry.location as u64, mutation_type: mutation_type};
-                apply_mutation(&mut mutated_ast, loaded_mutation);
+                let location = NodeLocation{line: mutation_entry.line as usize, column: mutation_entry.column as usize, node: mutation_entry.node.clone()};
+                let loaded_mutation = Mutation{location, mutation_type};
+                if !apply_mutation(&mut mutated_ast, loaded_mutation) {
+                    // Running the tests against the original program would report the mutation as surviving
+                    process::exit(1);
+                }
+                write(applied_writer, &[1]).unwrap();
                 return mutated_ast;
             }
 
@@ -147,9 +200,23 @@ fn execute(command_line_options: CommandLineOptions) {
 
         let run_result = run_script_with_timeout(&command_line_options.file, callback, Duration::new(1, 0));
 
+        // Nothing is written if no loaded module has the explored source, or if the node can't be found in it
+        close(applied_writer).unwrap();
+        let mut applied = [0u8; 1];
+        let mutation_applied = read(applied_reader, &mut applied).unwrap_or(0) > 0;
+        close(applied_reader).unwrap();
+
+        let run_result = match run_result {
+            RunResult::Timeout => RunResult::Timeout,
+            _ if !mutation_applied => RunResult::NotFound,
+            run_result => run_result,
+        };
+
         let entry = ResultEntry {
             file_sha1: mutation_entry_copy.file_sha1,
-            location: mutation_entry_copy.location,
+            line: mutation_entry_copy.line,
+            column: mutation_entry_copy.column,
+            node: mutation_entry_copy.node,
             mutation: mutation_entry_copy.mutation,
             test_runner_sha1: test_runner_hash.clone(),
             result: run_result.to_string(),
@@ -163,54 +230,82 @@ fn execute(command_line_options: CommandLineOptions) {
         counter += 1;
         print!("Finished {} of {}                  \r", counter, total_mutations);
     }
-    println!("Results stored in {}", &command_line_options.database);
+    println!("Results stored in {}", &database);
 }
 
 fn explore(command_line_options: CommandLineOptions) {
-    let conn = SqliteConnection::establish(&command_line_options.database).unwrap();
+    let database = required_database(&command_line_options);
+    let conn = SqliteConnection::establish(&database).unwrap();
 
     conn.execute(
         "create table if not exists mutations (
             file_sha1 text,
-            location integer,
+            line integer,
+            column integer,
+            node text,
             mutation text,
-            primary key (file_sha1, location, mutation)
+            source text,
+            diff text,
+            primary key (file_sha1, line, column, node, mutation)
         )"
     ).unwrap();
 
+    check_columns(&conn, &database, "mutations", "file_sha1, line, column, node, mutation, source, diff");
+
     let file = fs::read_to_string(&command_line_options.file).expect("");
     let mut program: ast::Program = parser::parse_program(&file).unwrap();
 
-    let found_mutations: Vec<Mutation> = explore_mutations(&mut program);
+    let found_mutations: Vec<Mutation> = explore_mutations(&mut program, &file);
+    let original_source = unparse_program(&program);
 
     println!("Found {} potential mutations in {}", found_mutations.len(), &command_line_options.file);
     let mut counter: u64 = 0;
 
     for found_mutation in found_mutations {
         let mut mutated_program = program.clone();
-        apply_mutation(&mut mutated_program, found_mutation.clone());
+        if !apply_mutation(&mut mutated_program, found_mutation.clone()) {
+            eprintln!("Warning: no {} node found at {}:{}:{}, skipping mutation", found_mutation.location.node, &command_line_options.file, found_mutation.location.line, found_mutation.location.column);
+            continue;
+        }
 
         // Skip if mutation does nothing
         if mutated_program == program {
             continue;
         }
 
+        // Both sides are unparsed so that only the mutation shows up in the diff
+        let mutated_source = unparse_program(&mutated_program);
+        let mutated_lines = changed_lines(&original_source, &mutated_source);
+        let mutant_diff = unified_diff(&original_source, &mutated_source, &command_line_options.file, found_mutation.location.line);
+
         use schema::mutations::dsl::*;
 
         let entry = MutationEntry {
             file_sha1: hex::encode(Sha1::digest(file.as_bytes()).as_slice()),
-            location: found_mutation.traversal_location as i32,
+            line: found_mutation.location.line as i32,
+            column: found_mutation.location.column as i32,
+            node: found_mutation.location.node.clone(),
             mutation: serde_json::to_string(&found_mutation.mutation_type).unwrap(),
+            source: mutated_lines.clone(),
+            diff: mutant_diff,
         };
 
         if let Err(_) = insert_into(mutations).values(entry).execute(&conn) {
             continue;
         }
 
+        println!("{}:{}:{}: {}", &command_line_options.file, found_mutation.location.line, found_mutation.location.column, mutated_lines.trim());
         counter += 1;
     }
 
-    println!("Added {} mutations from {} to {}", counter, &command_line_options.file, &command_line_options.database);
+    println!("Added {} mutations from {} to {}", counter, &command_line_options.file, &database);
+}
+
+fn unparse(command_line_options: CommandLineOptions) {
+    let file = fs::read_to_string(&command_line_options.file).expect("");
+    let program: ast::Program = parser::parse_program(&file).unwrap();
+
+    print!("{}", unparse_program(&program));
 }
 
 fn main() {
@@ -219,6 +314,7 @@ fn main() {
     match command_line_options.mode {
         Mode::Execute => execute(command_line_options),
         Mode::Explore => explore(command_line_options),
+        Mode::Unparse => unparse(command_line_options),
     }
 }
 
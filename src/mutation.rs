@@ -1,95 +1,1369 @@
+use std::collections::HashSet;
 use rustpython_parser::ast;
 use serde::{Serialize, Deserialize};
 
-use crate::traversal::Visitor;
+use crate::traversal::{Visitor, StatementVisitor, expression_kind, statement_kind};
+use crate::scope::build_scope_table;
+use crate::pragma::skipped_lines;
 
 use crate::serde_compatibility::OperatorSerde;
+use crate::serde_compatibility::BooleanOperatorSerde;
+use crate::serde_compatibility::UnaryOperatorSerde;
 use crate::serde_compatibility::ComparisonSerde;
 use crate::serde_compatibility::NumberSerde;
 
+// The source position of a node together with its kind, such as `Binop` or `If`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct NodeLocation {
+    pub line: usize,
+    pub column: usize,
+    pub node: String,
+}
+
+impl NodeLocation {
+    pub fn of_expression(expression: &ast::Expression) -> NodeLocation {
+        NodeLocation {
+            line: expression.location.row(),
+            column: expression.location.column(),
+            node: String::from(expression_kind(&expression.node)),
+        }
+    }
+
+    pub fn of_statement(statement: &ast::Statement) -> NodeLocation {
+        NodeLocation {
+            line: statement.location.row(),
+            column: statement.location.column(),
+            node: String::from(statement_kind(&statement.node)),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Mutation {
-    pub traversal_location: u64,
+    pub location: NodeLocation,
     pub mutation_type: MutationType,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-pub enum MutationType {
-    #[serde(with = "OperatorSerde")]
-    BinaryOperatorReplacement {new_operator: ast::Operator},
+#[derive(Serialize, Deserialize, Clone)]
+pub enum MutationType {
+    #[serde(with = "OperatorSerde")]
+    BinaryOperatorReplacement {new_operator: ast::Operator},
+
+    #[serde(with = "BooleanOperatorSerde")]
+    BooleanOperatorReplacement {new_operator: ast::BooleanOperator},
+
+    BooleanOperandReplacement {index: usize},
+
+    UnaryOperatorRemoval,
+
+    #[serde(with = "UnaryOperatorSerde")]
+    UnaryOperatorReplacement {new_operator: ast::UnaryOperator},
+
+    #[serde(with = "UnaryOperatorSerde")]
+    UnaryOperatorInsertion {new_operator: ast::UnaryOperator},
+
+    ComparisonOperatorReplacement {
+        index: usize,
+        #[serde(with = "ComparisonSerde")]
+        new_operator: ast::Comparison,
+    },
+
+    #[serde(with = "NumberSerde")]
+    NumberConstantReplacement {new_constant: ast::Number},
+
+    ConstantReplacement {new_constant: Constant},
+
+    StringConstantReplacement {new_constant: String},
+
+    BytesConstantReplacement {new_constant: Vec<u8>},
+
+    KeywordArgumentRemoval {index: usize},
+
+    ArgumentSwap {index: usize},
+
+    CallResultReplacement {index: usize},
+
+    IndexShift {offset: i64},
+
+    IndexReplacement {new_index: i64},
+
+    SliceBoundShift {index: usize, offset: i64},
+
+    SliceBoundRemoval {index: usize},
+
+    SliceBoundInsertion {index: usize, new_bound: i64},
+
+    ElementRemoval {index: usize},
+
+    ComprehensionFilterRemoval {generator: usize, index: usize},
+
+    ComprehensionFilterNegation {generator: usize, index: usize},
+
+    DictComprehensionSwap,
+
+    IdentifierReplacement {new_name: String},
+
+    AwaitRemoval,
+
+    YieldValueReplacement,
+
+    LambdaBodyReplacement,
+
+    #[serde(with = "OperatorSerde")]
+    AugmentedAssignmentReplacement {new_operator: ast::Operator},
+
+    StatementDeletion,
+
+    ReturnValueReplacement {new_value: Constant},
+
+    LoopControlSwap,
+
+    ZeroIterationLoop,
+
+    LoopElseRemoval,
+
+    VoidCallRemoval,
+
+    ExceptionTypeBroadening {handler: usize},
+
+    HandlerBodyReplacement {handler: usize},
+
+    FinallyRemoval,
+
+    DecoratorRemoval {index: usize},
+
+    DefaultArgumentReplacement {keyword_only: bool, index: usize, new_value: Constant},
+
+    ContextManagerRemoval,
+
+    BaseClassRemoval {index: usize},
+
+    MethodRemoval {index: usize},
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum Constant {
+    True,
+    False,
+    None,
+    // A fresh `object()`, which is distinct from any other value
+    Sentinel,
+    EmptyList,
+    EmptyTuple,
+    EmptyDict,
+    EmptySet,
+}
+
+impl Constant {
+    pub fn to_expression(&self, location: ast::Location) -> ast::Expression {
+        let node = match self {
+            Constant::True => ast::ExpressionType::True {},
+            Constant::False => ast::ExpressionType::False {},
+            Constant::None => ast::ExpressionType::None {},
+            Constant::Sentinel => ast::ExpressionType::Call {
+                function: Box::new(ast::Expression {location: location.clone(), node: ast::ExpressionType::Identifier {name: String::from("object")}}),
+                args: Vec::new(),
+                keywords: Vec::new(),
+            },
+            Constant::EmptyList => ast::ExpressionType::List {elements: Vec::new()},
+            Constant::EmptyTuple => ast::ExpressionType::Tuple {elements: Vec::new()},
+            Constant::EmptyDict => ast::ExpressionType::Dict {elements: Vec::new()},
+            // `{}` is an empty dict
+            Constant::EmptySet => ast::ExpressionType::Call {
+                function: Box::new(ast::Expression {location: location.clone(), node: ast::ExpressionType::Identifier {name: String::from("set")}}),
+                args: Vec::new(),
+                keywords: Vec::new(),
+            },
+        };
+
+        ast::Expression {location, node}
+    }
+}
+
+impl MutationType {
+    // Statement mutations are looked up among statements instead of expressions
+    pub fn targets_statement(&self) -> bool {
+        matches!(self,
+            MutationType::AugmentedAssignmentReplacement {..} |
+            MutationType::StatementDeletion |
+            MutationType::ReturnValueReplacement {..} |
+            MutationType::LoopControlSwap |
+            MutationType::ZeroIterationLoop |
+            MutationType::LoopElseRemoval |
+            MutationType::VoidCallRemoval |
+            MutationType::ExceptionTypeBroadening {..} |
+            MutationType::HandlerBodyReplacement {..} |
+            MutationType::FinallyRemoval |
+            MutationType::DecoratorRemoval {..} |
+            MutationType::DefaultArgumentReplacement {..} |
+            MutationType::ContextManagerRemoval |
+            MutationType::BaseClassRemoval {..} |
+            MutationType::MethodRemoval {..}
+        )
+    }
+}
+
+pub trait Mutate {
+    fn mutate(&mut self, mutation_type: MutationType);
+}
+
+impl Mutate for ast::Expression {
+    fn mutate(&mut self, mutation_type: MutationType) {
+        match mutation_type {
+
+            MutationType::BinaryOperatorReplacement {new_operator} => {
+                match &mut self.node {
+                    ast::ExpressionType::Binop {a: _, op, b: _} => {
+                        *op = new_operator;
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::BooleanOperatorReplacement {new_operator} => {
+                match &mut self.node {
+                    ast::ExpressionType::BoolOp {op, values: _} => {
+                        *op = new_operator;
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::BooleanOperandReplacement {index} => {
+                let operand = match &self.node {
+                    ast::ExpressionType::BoolOp {op: _, values} => values[index].clone(),
+
+                    _ => unreachable!(),
+                };
+
+                *self = operand;
+            },
+
+            MutationType::UnaryOperatorRemoval => {
+                let operand = match &self.node {
+                    ast::ExpressionType::Unop {op: _, a} => (**a).clone(),
+
+                    _ => unreachable!(),
+                };
+
+                *self = operand;
+            },
+
+            MutationType::UnaryOperatorReplacement {new_operator} => {
+                match &mut self.node {
+                    ast::ExpressionType::Unop {op, a: _} => {
+                        *op = new_operator;
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::UnaryOperatorInsertion {new_operator} => {
+                let operand = self.clone();
+                self.node = ast::ExpressionType::Unop {op: new_operator, a: Box::new(operand)};
+            },
+
+            MutationType::ComparisonOperatorReplacement {index, new_operator} => {
+                match &mut self.node {
+                    ast::ExpressionType::Compare {vals: _, ops} => {
+                        ops[index] = new_operator;
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::NumberConstantReplacement {new_constant} => {
+                match &mut self.node {
+                    ast::ExpressionType::Number {value} => {
+                        *value = new_constant;
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::ConstantReplacement {new_constant} => {
+                *self = new_constant.to_expression(self.location.clone());
+            },
+
+            MutationType::StringConstantReplacement {new_constant} => {
+                match &mut self.node {
+                    ast::ExpressionType::String {value} => {
+                        *value = ast::StringGroup::Constant {value: new_constant};
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::BytesConstantReplacement {new_constant} => {
+                match &mut self.node {
+                    ast::ExpressionType::Bytes {value} => {
+                        *value = new_constant;
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::KeywordArgumentRemoval {index} => {
+                match &mut self.node {
+                    ast::ExpressionType::Call {function: _, args: _, keywords} => {
+                        keywords.remove(index);
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::ArgumentSwap {index} => {
+                match &mut self.node {
+                    ast::ExpressionType::Call {function: _, args, keywords: _} => {
+                        args.swap(index, index + 1);
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::CallResultReplacement {index} => {
+                let argument = match &self.node {
+                    ast::ExpressionType::Call {function: _, args, keywords: _} => args[index].clone(),
+
+                    _ => unreachable!(),
+                };
+
+                *self = argument;
+            },
+
+            MutationType::IndexShift {offset} => {
+                match &mut self.node {
+                    ast::ExpressionType::Subscript {a: _, b} => {
+                        let index = shifted(b, offset);
+                        **b = index;
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::IndexReplacement {new_index} => {
+                match &mut self.node {
+                    ast::ExpressionType::Subscript {a: _, b} => {
+                        **b = integer(new_index, b.location.clone());
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::SliceBoundShift {index, offset} => {
+                match &mut self.node {
+                    ast::ExpressionType::Slice {elements} => {
+                        let bound = shifted(&elements[index], offset);
+                        elements[index] = bound;
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::SliceBoundRemoval {index} => {
+                match &mut self.node {
+                    ast::ExpressionType::Slice {elements} => {
+                        elements[index] = Constant::None.to_expression(elements[index].location.clone());
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::SliceBoundInsertion {index, new_bound} => {
+                match &mut self.node {
+                    ast::ExpressionType::Slice {elements} => {
+                        elements[index] = integer(new_bound, elements[index].location.clone());
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::ElementRemoval {index} => {
+                match &mut self.node {
+                    ast::ExpressionType::List {elements} |
+                    ast::ExpressionType::Tuple {elements} |
+                    ast::ExpressionType::Set {elements} => {
+                        elements.remove(index);
+                    },
+
+                    ast::ExpressionType::Dict {elements} => {
+                        elements.remove(index);
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::ComprehensionFilterRemoval {generator, index} => {
+                match &mut self.node {
+                    ast::ExpressionType::Comprehension {kind: _, generators} => {
+                        generators[generator].ifs.remove(index);
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::ComprehensionFilterNegation {generator, index} => {
+                match &mut self.node {
+                    ast::ExpressionType::Comprehension {kind: _, generators} => {
+                        let filter = &mut generators[generator].ifs[index];
+                        let operand = filter.clone();
+                        filter.node = ast::ExpressionType::Unop {op: ast::UnaryOperator::Not, a: Box::new(operand)};
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::IdentifierReplacement {new_name} => {
+                match &mut self.node {
+                    ast::ExpressionType::Identifier {name} => {
+                        *name = new_name;
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::AwaitRemoval => {
+                // The coroutine object itself is used instead of its result
+                let coroutine = match &self.node {
+                    ast::ExpressionType::Await {value} => (**value).clone(),
+
+                    _ => unreachable!(),
+                };
+
+                *self = coroutine;
+            },
+
+            MutationType::YieldValueReplacement => {
+                match &mut self.node {
+                    ast::ExpressionType::Yield {value: Some(value)} => {
+                        **value = Constant::None.to_expression(value.location.clone());
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::LambdaBodyReplacement => {
+                match &mut self.node {
+                    ast::ExpressionType::Lambda {args: _, body} => {
+                        **body = Constant::None.to_expression(body.location.clone());
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::DictComprehensionSwap => {
+                match &mut self.node {
+                    ast::ExpressionType::Comprehension {kind, generators: _} => {
+                        match &mut **kind {
+                            ast::ComprehensionKind::Dict {key, value} => std::mem::swap(key, value),
+
+                            _ => unreachable!(),
+                        }
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            _ => unreachable!(),
+
+        }
+    }
+}
+
+fn integer(value: i64, location: ast::Location) -> ast::Expression {
+    ast::Expression {location, node: ast::ExpressionType::Number {value: ast::Number::Integer {value: num_bigint::BigInt::from(value)}}}
+}
+
+// `expression + offset`, or `expression - offset` for negative offsets
+fn shifted(expression: &ast::Expression, offset: i64) -> ast::Expression {
+    let op = if offset < 0 { ast::Operator::Sub } else { ast::Operator::Add };
+    let amount = integer(offset.abs(), expression.location.clone());
+
+    ast::Expression {
+        location: expression.location.clone(),
+        node: ast::ExpressionType::Binop {a: Box::new(expression.clone()), op, b: Box::new(amount)},
+    }
+}
+
+pub trait MutateStatement {
+    fn mutate_statement(&mut self, index: usize, mutation_type: MutationType);
+}
+
+impl MutateStatement for Vec<ast::Statement> {
+    fn mutate_statement(&mut self, index: usize, mutation_type: MutationType) {
+        match mutation_type {
+
+            MutationType::AugmentedAssignmentReplacement {new_operator} => {
+                match &mut self[index].node {
+                    ast::StatementType::AugAssign {target: _, op, value: _} => {
+                        *op = new_operator;
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::StatementDeletion => {
+                // A block can not be empty, so a lone statement is replaced with `pass`
+                if self.len() > 1 {
+                    self.remove(index);
+                } else {
+                    self[index].node = ast::StatementType::Pass;
+                }
+            },
+
+            MutationType::ReturnValueReplacement {new_value} => {
+                match &mut self[index].node {
+                    ast::StatementType::Return {value: Some(value)} => {
+                        *value = new_value.to_expression(value.location.clone());
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::LoopControlSwap => {
+                let statement = &mut self[index];
+
+                statement.node = match statement.node {
+                    ast::StatementType::Break => ast::StatementType::Continue,
+                    ast::StatementType::Continue => ast::StatementType::Break,
+
+                    _ => unreachable!(),
+                };
+            },
+
+            MutationType::ZeroIterationLoop => {
+                match &mut self[index].node {
+                    ast::StatementType::For {is_async: _, target: _, iter, body: _, orelse: _} => {
+                        **iter = Constant::EmptyTuple.to_expression(iter.location.clone());
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::LoopElseRemoval => {
+                match &mut self[index].node {
+                    ast::StatementType::For {is_async: _, target: _, iter: _, body: _, orelse} => {
+                        *orelse = None;
+                    },
+
+                    ast::StatementType::While {test: _, body: _, orelse} => {
+                        *orelse = None;
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::VoidCallRemoval => {
+                match &mut self[index].node {
+                    ast::StatementType::Expression {expression} => {
+                        *expression = Constant::None.to_expression(expression.location.clone());
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::ExceptionTypeBroadening {handler} => {
+                match &mut self[index].node {
+                    ast::StatementType::Try {body: _, handlers, orelse: _, finalbody: _} => {
+                        let typ = &mut handlers[handler].typ;
+                        let location = typ.as_ref().unwrap().location.clone();
+                        *typ = Some(ast::Expression {location, node: ast::ExpressionType::Identifier {name: String::from("Exception")}});
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::HandlerBodyReplacement {handler} => {
+                match &mut self[index].node {
+                    ast::StatementType::Try {body: _, handlers, orelse: _, finalbody: _} => {
+                        let body = &mut handlers[handler].body;
+                        let location = body[0].location.clone();
+                        *body = vec![ast::Statement {location, node: ast::StatementType::Raise {exception: None, cause: None}}];
+                    },
+
+                    _ => unreachable!(),
+                }
+            },
+
+            MutationType::FinallyRemoval => {
+                let inlined_body = match &mut self[index].node {
+                    ast::StatementType::Try {body, handlers, orelse: _, finalbody} => {
+                        if handlers.is_empty() {
+                            // A try statement needs either handlers or a finally block, so the body is inlined instead
+                            Some(body.clone())
+                        } else {
+                            *finalbody = None;
+                            None
+                        }
+                    },
+
+                    _ => unreachable!(),
+                };
+
+                if let Some(body) = inlined_body {
+                    self.splice(index..index + 1, body);
+                }
+            },
+
+            MutationType::DecoratorRemoval {index: decorator} => {
+                match &mut self[index].node {
+                    ast::StatementType::FunctionDef {is_async: _, name: _, args: _, body: _, decorator_list, returns: _} => {
+                        decorator_list.remove(decorator);
+                    },
 
-    #[serde(with = "ComparisonSerde")]
-    ComparisonOperatorReplacement {new_operator: ast::Comparison},
+                    ast::StatementType::ClassDef {name: _, body: _, bases: _, keywords: _, decorator_list} => {
+                        decorator_list.remove(decorator);
+                    },
 
-    #[serde(with = "NumberSerde")]
-    NumberConstantReplacement {new_constant: ast::Number},
-}
+                    _ => unreachable!(),
+                }
+            },
 
-pub trait Mutate {
-    fn mutate(&mut self, mutation_type: MutationType);
-}
+            MutationType::ContextManagerRemoval => {
+                // `with ctx: body` becomes just `body`
+                let body = match &self[index].node {
+                    ast::StatementType::With {is_async: _, items: _, body} => body.clone(),
 
-impl Mutate for ast::Expression {
-    fn mutate(&mut self, mutation_type: MutationType) {
-        match mutation_type {
+                    _ => unreachable!(),
+                };
 
-            MutationType::BinaryOperatorReplacement {new_operator} => {
-                match &mut self.node {
-                    ast::ExpressionType::Binop {a: _, op, b: _} => {
-                        *op = new_operator;
+                self.splice(index..index + 1, body);
+            },
+
+            MutationType::BaseClassRemoval {index: base} => {
+                match &mut self[index].node {
+                    ast::StatementType::ClassDef {name: _, body: _, bases, keywords: _, decorator_list: _} => {
+                        bases.remove(base);
                     },
 
                     _ => unreachable!(),
                 }
             },
 
-            MutationType::ComparisonOperatorReplacement {new_operator} => {
-                match &mut self.node {
-                    ast::ExpressionType::Compare {vals: _, ops} => {
-                        ops[0] = new_operator;
+            MutationType::MethodRemoval {index: method} => {
+                match &mut self[index].node {
+                    // The method of the parent class is used instead, and a lone method is replaced with `pass`
+                    ast::StatementType::ClassDef {name: _, body, bases: _, keywords: _, decorator_list: _} => {
+                        if body.len() > 1 {
+                            body.remove(method);
+                        } else {
+                            body[method].node = ast::StatementType::Pass;
+                        }
                     },
 
                     _ => unreachable!(),
                 }
             },
 
-            MutationType::NumberConstantReplacement {new_constant} => {
-                match &mut self.node {
-                    ast::ExpressionType::Number {value} => {
-                        *value = new_constant;
+            MutationType::DefaultArgumentReplacement {keyword_only, index: default, new_value} => {
+                match &mut self[index].node {
+                    ast::StatementType::FunctionDef {is_async: _, name: _, args, body: _, decorator_list: _, returns: _} => {
+                        let value = if keyword_only {
+                            args.kw_defaults[default].as_mut().unwrap()
+                        } else {
+                            &mut args.defaults[default]
+                        };
+
+                        *value = new_value.to_expression(value.location.clone());
                     },
 
                     _ => unreachable!(),
                 }
             },
 
-            //_ => unreachable!(),
+            _ => unreachable!(),
 
         }
     }
 }
 
-pub fn explore_mutations(program: &mut ast::Program) -> Vec<Mutation> {
+fn operator_replacements(operator: &ast::Operator) -> Vec<ast::Operator> {
+    match operator {
+        ast::Operator::Add => vec![ast::Operator::Sub],
+        ast::Operator::Sub => vec![ast::Operator::Add],
+        ast::Operator::Mult => vec![ast::Operator::Div],
+        ast::Operator::MatMult => vec![ast::Operator::Mult],
+        ast::Operator::Div => vec![ast::Operator::FloorDiv, ast::Operator::Mult],
+        ast::Operator::Mod => vec![ast::Operator::Mult],
+        ast::Operator::Pow => vec![ast::Operator::Mult],
+        ast::Operator::LShift => vec![ast::Operator::RShift],
+        ast::Operator::RShift => vec![ast::Operator::LShift],
+        ast::Operator::BitOr => vec![ast::Operator::BitAnd],
+        ast::Operator::BitXor => vec![ast::Operator::BitAnd],
+        ast::Operator::BitAnd => vec![ast::Operator::BitOr],
+        ast::Operator::FloorDiv => vec![ast::Operator::Div],
+    }
+}
+
+fn comparison_replacements(operator: &ast::Comparison) -> Vec<ast::Comparison> {
+    match operator {
+        ast::Comparison::Less => vec![ast::Comparison::LessOrEqual],
+        ast::Comparison::LessOrEqual => vec![ast::Comparison::Less],
+        ast::Comparison::Greater => vec![ast::Comparison::GreaterOrEqual],
+        ast::Comparison::GreaterOrEqual => vec![ast::Comparison::Greater],
+        ast::Comparison::Equal => vec![ast::Comparison::NotEqual],
+        ast::Comparison::NotEqual => vec![ast::Comparison::Equal],
+        ast::Comparison::In => vec![ast::Comparison::NotIn],
+        ast::Comparison::NotIn => vec![ast::Comparison::In],
+        ast::Comparison::Is => vec![ast::Comparison::IsNot],
+        ast::Comparison::IsNot => vec![ast::Comparison::Is],
+    }
+}
+
+fn is_none(expression: &ast::Expression) -> bool {
+    matches!(expression.node, ast::ExpressionType::None {})
+}
+
+// The "empty" value of the same type as a container, e.g. `[]` for `[a, b]`. Scalar literals have their own constant mutations.
+fn empty_value(expression: &ast::Expression) -> Option<Constant> {
+    match &expression.node {
+        ast::ExpressionType::List {elements} if !elements.is_empty() => Some(Constant::EmptyList),
+        ast::ExpressionType::Tuple {elements} if !elements.is_empty() => Some(Constant::EmptyTuple),
+        ast::ExpressionType::Dict {elements} if !elements.is_empty() => Some(Constant::EmptyDict),
+        ast::ExpressionType::Set {elements} if !elements.is_empty() => Some(Constant::EmptySet),
+        ast::ExpressionType::Comprehension {kind, generators: _} => {
+            match &**kind {
+                ast::ComprehensionKind::List {element: _} => Some(Constant::EmptyList),
+                ast::ComprehensionKind::Dict {key: _, value: _} => Some(Constant::EmptyDict),
+                ast::ComprehensionKind::Set {element: _} => Some(Constant::EmptySet),
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
+
+fn is_starred(expression: &ast::Expression) -> bool {
+    matches!(expression.node, ast::ExpressionType::Starred {value: _})
+}
+
+fn is_number(expression: &ast::Expression) -> bool {
+    matches!(expression.node, ast::ExpressionType::Number {value: _})
+}
+
+fn is_name(expression: &ast::Expression, name: &str) -> bool {
+    match &expression.node {
+        ast::ExpressionType::Identifier {name: identifier} => identifier == name,
+        _ => false,
+    }
+}
+
+fn is_bare_raise(suite: &[ast::Statement]) -> bool {
+    match suite {
+        [statement] => matches!(statement.node, ast::StatementType::Raise {exception: None, cause: None}),
+        _ => false,
+    }
+}
+
+fn is_call(expression: &ast::Expression) -> bool {
+    matches!(expression.node, ast::ExpressionType::Call {function: _, args: _, keywords: _})
+}
+
+fn default_replacements(default: &ast::Expression) -> Vec<Constant> {
+    let mut replacements: Vec<Constant> = Vec::new();
+
+    // A `None` default is replaced by a value the function can't be expecting
+    if is_none(default) {
+        replacements.push(Constant::Sentinel);
+    } else {
+        replacements.push(Constant::None);
+    }
+
+    match &default.node {
+        ast::ExpressionType::True {} => replacements.push(Constant::False),
+        ast::ExpressionType::False {} => replacements.push(Constant::True),
+        ast::ExpressionType::List {elements: _} => replacements.extend(empty_value(default)),
+        ast::ExpressionType::Tuple {elements: _} => replacements.extend(empty_value(default)),
+        ast::ExpressionType::Dict {elements: _} => replacements.extend(empty_value(default)),
+        ast::ExpressionType::Set {elements: _} => replacements.extend(empty_value(default)),
+        _ => (),
+    }
+
+    replacements
+}
+
+fn is_deletable(statement: &ast::Statement) -> bool {
+    match &statement.node {
+        ast::StatementType::Pass => false,
+
+        // Removing a definition or import only leads to a NameError
+        ast::StatementType::Import {..} => false,
+        ast::StatementType::ImportFrom {..} => false,
+        ast::StatementType::Global {..} => false,
+        ast::StatementType::Nonlocal {..} => false,
+        ast::StatementType::ClassDef {..} => false,
+        ast::StatementType::FunctionDef {..} => false,
+
+        // Docstrings, and calls which are covered by void call removal
+        ast::StatementType::Expression {expression} => !matches!(expression.node, ast::ExpressionType::String {..}) && !is_call(expression),
+
+        _ => true,
+    }
+}
+
+pub fn explore_mutations(program: &mut ast::Program, source: &str) -> Vec<Mutation> {
     let mut mutations: Vec<Mutation> = Vec::new();
-    let mut i: u64 = 0;
+
+    // Nodes are recorded by kind as well as position, since e.g. `a - b` starts where `a` does
+
+    // Locations of the tests of if statements, while statements and conditional expressions
+    let mut conditions: HashSet<NodeLocation> = HashSet::new();
+
+    // Locations of `None` used as a return value
+    let mut optional_values: HashSet<NodeLocation> = HashSet::new();
+
+    // Locations of parameter defaults and return values, which have their own constant mutations
+    let mut parameter_defaults: HashSet<NodeLocation> = HashSet::new();
+    let mut return_values: HashSet<NodeLocation> = HashSet::new();
+
+    // Locations of expressions which are assigned to, and everything within them, such as `a, b` in `a, b = b, a`
+    let mut assignment_targets: HashSet<NodeLocation> = HashSet::new();
+
+    // Locations of tuples used as a multidimensional index, such as `i, j` in `a[i, j]`
+    let mut subscript_indices: HashSet<NodeLocation> = HashSet::new();
+
+    // Locations of strings used as statements, such as docstrings
+    let mut docstrings: HashSet<NodeLocation> = HashSet::new();
+
+    // Locations of calls whose result is discarded
+    let mut void_calls: HashSet<NodeLocation> = HashSet::new();
+
+    // Locations of every expression within a parameter, return or variable annotation
+    let mut annotations: HashSet<NodeLocation> = HashSet::new();
+
+    let scope_table = build_scope_table(program);
+
+    // Nodes which share a position and kind with an earlier node can not be told apart, so they are not mutated
+    let mut seen_statements: HashSet<NodeLocation> = HashSet::new();
+    let mut seen_expressions: HashSet<NodeLocation> = HashSet::new();
+
+    program.visit_statements(&mut |suite, index| {
+        let location = NodeLocation::of_statement(&suite[index]);
+        let ambiguous = seen_statements.contains(&location);
+        let first_mutation = mutations.len();
+
+        if is_deletable(&suite[index]) {
+            let mutation = MutationType::StatementDeletion;
+            mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+        }
+
+        {
+            let mut record = |expr: &mut ast::Expression| { annotations.insert(NodeLocation::of_expression(expr)); };
+            let mut record_target = |expr: &mut ast::Expression| { assignment_targets.insert(NodeLocation::of_expression(expr)); };
+
+            match &mut suite[index].node {
+                ast::StatementType::FunctionDef {is_async: _, name: _, args, body: _, decorator_list: _, returns} => {
+                    args.args.visit(&mut record);
+                    args.kwonlyargs.visit(&mut record);
+                    args.vararg.visit(&mut record);
+                    args.kwarg.visit(&mut record);
+                    returns.visit(&mut record);
+                },
+                ast::StatementType::AnnAssign {target, annotation, value: _} => {
+                    annotation.visit(&mut record);
+                    target.visit(&mut record_target);
+                },
+                ast::StatementType::For {is_async: _, target, iter: _, body: _, orelse: _} => target.visit(&mut record_target),
+                ast::StatementType::Assign {targets, value: _} => targets.visit(&mut record_target),
+                ast::StatementType::Delete {targets} => targets.visit(&mut record_target),
+                ast::StatementType::With {is_async: _, items, body: _} => {
+                    for item in items {
+                        item.optional_vars.visit(&mut record_target);
+                    }
+                },
+                _ => (),
+            }
+        }
+
+        match &suite[index].node {
+
+            ast::StatementType::If {test, body: _, orelse: _} => { conditions.insert(NodeLocation::of_expression(test)); },
+
+            ast::StatementType::While {test, body: _, orelse} => {
+                // Replacing the test with `False` already skips the loop
+                conditions.insert(NodeLocation::of_expression(test));
+
+                if orelse.is_some() {
+                    let mutation = MutationType::LoopElseRemoval;
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::StatementType::For {is_async: _, target: _, iter: _, body: _, orelse} => {
+                {
+                    let mutation = MutationType::ZeroIterationLoop;
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+
+                if orelse.is_some() {
+                    let mutation = MutationType::LoopElseRemoval;
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::StatementType::With {is_async: _, items: _, body: _} => {
+                let mutation = MutationType::ContextManagerRemoval;
+                mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+            },
+
+            ast::StatementType::Try {body: _, handlers, orelse: _, finalbody} => {
+                // Removing a raise statement is covered by statement deletion
+                for (handler, except_handler) in handlers.iter().enumerate() {
+                    if let Some(typ) = &except_handler.typ {
+                        if !is_name(typ, "Exception") && !is_name(typ, "BaseException") {
+                            let mutation = MutationType::ExceptionTypeBroadening{handler};
+                            mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                        }
+                    }
+
+                    if !is_bare_raise(&except_handler.body) {
+                        let mutation = MutationType::HandlerBodyReplacement{handler};
+                        mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                    }
+                }
+
+                if finalbody.is_some() {
+                    let mutation = MutationType::FinallyRemoval;
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::StatementType::Break => {
+                let mutation = MutationType::LoopControlSwap;
+                mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+            },
+
+            ast::StatementType::Continue => {
+                let mutation = MutationType::LoopControlSwap;
+                mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+            },
+
+            ast::StatementType::AugAssign {target: _, op, value: _} => {
+                for new_operator in operator_replacements(op) {
+                    let mutation = MutationType::AugmentedAssignmentReplacement{new_operator};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::StatementType::Expression {expression} => {
+                if let ast::ExpressionType::String {value: _} = expression.node {
+                    docstrings.insert(NodeLocation::of_expression(expression));
+                }
+
+                if is_call(expression) {
+                    void_calls.insert(NodeLocation::of_expression(expression));
+
+                    let mutation = MutationType::VoidCallRemoval;
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::StatementType::Return {value: Some(value)} => {
+                return_values.insert(NodeLocation::of_expression(value));
+
+                if is_none(value) {
+                    optional_values.insert(NodeLocation::of_expression(value));
+                } else {
+                    let mutation = MutationType::ReturnValueReplacement{new_value: Constant::None};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+
+                if let Some(new_value) = empty_value(value) {
+                    let mutation = MutationType::ReturnValueReplacement{new_value};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::StatementType::FunctionDef {is_async: _, name: _, args, body: _, decorator_list, returns: _} => {
+                for decorator in 0..decorator_list.len() {
+                    let mutation = MutationType::DecoratorRemoval{index: decorator};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+
+                for (default, value) in args.defaults.iter().enumerate() {
+                    parameter_defaults.insert(NodeLocation::of_expression(value));
+
+                    for new_value in default_replacements(value) {
+                        let mutation = MutationType::DefaultArgumentReplacement{keyword_only: false, index: default, new_value};
+                        mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                    }
+                }
+
+                // Keyword only parameters without a default have no entry
+                for (default, value) in args.kw_defaults.iter().enumerate() {
+                    if let Some(value) = value {
+                        parameter_defaults.insert(NodeLocation::of_expression(value));
+
+                        for new_value in default_replacements(value) {
+                            let mutation = MutationType::DefaultArgumentReplacement{keyword_only: true, index: default, new_value};
+                            mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                        }
+                    }
+                }
+            },
+
+            ast::StatementType::ClassDef {name: _, body, bases, keywords: _, decorator_list} => {
+                for decorator in 0..decorator_list.len() {
+                    let mutation = MutationType::DecoratorRemoval{index: decorator};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+
+                // Every class already derives from `object`
+                for (base, expression) in bases.iter().enumerate() {
+                    if !is_name(expression, "object") {
+                        let mutation = MutationType::BaseClassRemoval{index: base};
+                        mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                    }
+                }
+
+                // Only methods of a subclass can override anything
+                if bases.iter().any(|base| !is_name(base, "object")) {
+                    for (method, statement) in body.iter().enumerate() {
+                        if let ast::StatementType::FunctionDef {..} = statement.node {
+                            let mutation = MutationType::MethodRemoval{index: method};
+                            mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                        }
+                    }
+                }
+            },
+
+            _ => (),
+        }
+
+        if ambiguous {
+            mutations.truncate(first_mutation);
+        } else {
+            seen_statements.insert(location);
+        }
+    });
 
     program.visit(&mut |expr| {
-        i += 1;
+        let location = NodeLocation::of_expression(expr);
+
+        // Annotations are only evaluated when the definition runs, so their mutants either break it, e.g. `List[str]` to `List[0]`, or change nothing
+        if annotations.contains(&location) {
+            return;
+        }
+
+        let ambiguous = seen_expressions.contains(&location);
+        let first_mutation = mutations.len();
+
+        // Expressions are visited before their children, so comprehension targets are recorded before they are reached
+        if let ast::ExpressionType::Comprehension {kind: _, generators} = &mut expr.node {
+            for comprehension in generators.iter_mut() {
+                comprehension.target.visit(&mut |target| { assignment_targets.insert(NodeLocation::of_expression(target)); });
+            }
+        }
+
+        if conditions.contains(&location) {
+            let condition_mutations = [
+                MutationType::UnaryOperatorInsertion{new_operator: ast::UnaryOperator::Not},
+                MutationType::ConstantReplacement{new_constant: Constant::True},
+                MutationType::ConstantReplacement{new_constant: Constant::False},
+            ];
+
+            for mutation in condition_mutations {
+                mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+            }
+        }
 
         match &expr.node {
 
-            ast::ExpressionType::Binop {..} => {
-                {
-                    let mutation = MutationType::BinaryOperatorReplacement{new_operator: ast::Operator::Mult};
-                    mutations.push(Mutation{traversal_location: i, mutation_type: mutation});
+            ast::ExpressionType::Binop {a: _, op, b: _} => {
+                for new_operator in operator_replacements(op) {
+                    let mutation = MutationType::BinaryOperatorReplacement{new_operator};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
                 }
+            },
+
+            ast::ExpressionType::BoolOp {op, values} => {
                 {
-                    let mutation = MutationType::BinaryOperatorReplacement{new_operator: ast::Operator::Sub};
-                    mutations.push(Mutation{traversal_location: i, mutation_type: mutation});
+                    let new_operator = match op {
+                        ast::BooleanOperator::And => ast::BooleanOperator::Or,
+                        ast::BooleanOperator::Or => ast::BooleanOperator::And,
+                    };
+                    let mutation = MutationType::BooleanOperatorReplacement{new_operator};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
                 }
-                {
-                    let mutation = MutationType::BinaryOperatorReplacement{new_operator: ast::Operator::Pow};
-                    mutations.push(Mutation{traversal_location: i, mutation_type: mutation});
+                // Replace the whole expression with a single operand, e.g. `a and b` becomes `a`
+                for index in 0..values.len() {
+                    let mutation = MutationType::BooleanOperandReplacement{index};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::ExpressionType::Unop {op, a: _} => {
+                let unary_mutations = match op {
+                    ast::UnaryOperator::Pos => vec![
+                        MutationType::UnaryOperatorReplacement{new_operator: ast::UnaryOperator::Neg},
+                    ],
+                    ast::UnaryOperator::Neg => vec![
+                        MutationType::UnaryOperatorRemoval,
+                        MutationType::UnaryOperatorReplacement{new_operator: ast::UnaryOperator::Pos},
+                    ],
+                    ast::UnaryOperator::Not => vec![MutationType::UnaryOperatorRemoval],
+                    ast::UnaryOperator::Inv => vec![MutationType::UnaryOperatorRemoval],
+                };
+
+                for mutation in unary_mutations {
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::ExpressionType::Compare {vals: _, ops} => {
+                // Every operator of a chained comparison is mutated on its own
+                for (index, op) in ops.iter().enumerate() {
+                    for new_operator in comparison_replacements(op) {
+                        let mutation = MutationType::ComparisonOperatorReplacement{index, new_operator};
+                        mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                    }
+                }
+            },
+
+            ast::ExpressionType::Call {function: _, args, keywords} => {
+                // `**kwargs` has no name
+                for (index, keyword) in keywords.iter().enumerate() {
+                    if keyword.name.is_some() {
+                        let mutation = MutationType::KeywordArgumentRemoval{index};
+                        mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                    }
+                }
+
+                for (index, pair) in args.windows(2).enumerate() {
+                    if !is_starred(&pair[0]) && !is_starred(&pair[1]) {
+                        let mutation = MutationType::ArgumentSwap{index};
+                        mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                    }
+                }
+
+                if !void_calls.contains(&location) {
+                    for (index, argument) in args.iter().enumerate() {
+                        if !is_starred(argument) {
+                            let mutation = MutationType::CallResultReplacement{index};
+                            mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                        }
+                    }
+                }
+            },
+
+            ast::ExpressionType::Subscript {a: _, b} => {
+                match &b.node {
+                    ast::ExpressionType::Slice {elements: _} => (),
+                    // Multidimensional indexing, e.g. `a[i, j]`. The index is visited next.
+                    ast::ExpressionType::Tuple {elements: _} => { subscript_indices.insert(NodeLocation::of_expression(b)); },
+                    // Keys which are not integers, e.g. `config["timeout"]`
+                    ast::ExpressionType::String {..} |
+                    ast::ExpressionType::Bytes {..} |
+                    ast::ExpressionType::True {} |
+                    ast::ExpressionType::False {} |
+                    ast::ExpressionType::None {} |
+                    ast::ExpressionType::List {..} |
+                    ast::ExpressionType::Dict {..} |
+                    ast::ExpressionType::Set {..} |
+                    ast::ExpressionType::Comprehension {..} => (),
+
+                    _ => {
+                        // Literal indices are already covered by number constant mutations
+                        if !is_number(b) {
+                            for offset in [1, -1] {
+                                let mutation = MutationType::IndexShift{offset};
+                                mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                            }
+                        }
+
+                        for new_index in [0, -1] {
+                            let mutation = MutationType::IndexReplacement{new_index};
+                            mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                        }
+                    },
+                }
+            },
+
+            ast::ExpressionType::Slice {elements} => {
+                // Omitted bounds are `None`, and only the start and stop are mutated
+                for (index, bound) in elements.iter().enumerate().take(2) {
+                    if is_none(bound) {
+                        // Skip the first element, or drop the last one
+                        let new_bound = if index == 0 { 1 } else { -1 };
+                        let mutation = MutationType::SliceBoundInsertion{index, new_bound};
+                        mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                        continue;
+                    }
+
+                    {
+                        let mutation = MutationType::SliceBoundRemoval{index};
+                        mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                    }
+
+                    if !is_number(bound) {
+                        for offset in [1, -1] {
+                            let mutation = MutationType::SliceBoundShift{index, offset};
+                            mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                        }
+                    }
+                }
+            },
+
+            ast::ExpressionType::List {elements} |
+            ast::ExpressionType::Tuple {elements} |
+            ast::ExpressionType::Set {elements} => {
+                if !assignment_targets.contains(&location) && !subscript_indices.contains(&location) {
+                    if elements.len() > 1 {
+                        for index in 0..elements.len() {
+                            let mutation = MutationType::ElementRemoval{index};
+                            mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                        }
+                    }
+
+                    let empty = match &expr.node {
+                        ast::ExpressionType::List {elements: _} => Constant::EmptyList,
+                        ast::ExpressionType::Tuple {elements: _} => Constant::EmptyTuple,
+                        _ => Constant::EmptySet,
+                    };
+
+                    if !elements.is_empty() && !return_values.contains(&location) && !parameter_defaults.contains(&location) {
+                        let mutation = MutationType::ConstantReplacement{new_constant: empty};
+                        mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                    }
+                }
+            },
+
+            ast::ExpressionType::Dict {elements} => {
+                if elements.len() > 1 {
+                    for index in 0..elements.len() {
+                        let mutation = MutationType::ElementRemoval{index};
+                        mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                    }
+                }
+
+                if !elements.is_empty() && !return_values.contains(&location) && !parameter_defaults.contains(&location) {
+                    let mutation = MutationType::ConstantReplacement{new_constant: Constant::EmptyDict};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::ExpressionType::Comprehension {kind, generators} => {
+                for (generator, comprehension) in generators.iter().enumerate() {
+                    for index in 0..comprehension.ifs.len() {
+                        let filter_mutations = [
+                            MutationType::ComprehensionFilterRemoval{generator, index},
+                            MutationType::ComprehensionFilterNegation{generator, index},
+                        ];
+
+                        for mutation in filter_mutations {
+                            mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                        }
+                    }
+                }
+
+                if let ast::ComprehensionKind::Dict {key: _, value: _} = **kind {
+                    let mutation = MutationType::DictComprehensionSwap;
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::ExpressionType::Await {value: _} => {
+                let mutation = MutationType::AwaitRemoval;
+                mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+            },
+
+            ast::ExpressionType::Yield {value: Some(value)} => {
+                if !is_none(value) {
+                    let mutation = MutationType::YieldValueReplacement;
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::ExpressionType::Lambda {args: _, body} => {
+                if !is_none(body) {
+                    let mutation = MutationType::LambdaBodyReplacement;
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::ExpressionType::IfExpression {test, body: _, orelse: _} => {
+                // The test is visited next
+                conditions.insert(NodeLocation::of_expression(test));
+            },
+
+            ast::ExpressionType::Identifier {name: _} => {
+                if let Some(names) = scope_table.get(&location) {
+                    for new_name in names {
+                        let mutation = MutationType::IdentifierReplacement{new_name: new_name.clone()};
+                        mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                    }
+                }
+            },
+
+            ast::ExpressionType::True {} => {
+                if !parameter_defaults.contains(&location) {
+                    let mutation = MutationType::ConstantReplacement{new_constant: Constant::False};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::ExpressionType::False {} => {
+                if !parameter_defaults.contains(&location) {
+                    let mutation = MutationType::ConstantReplacement{new_constant: Constant::True};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::ExpressionType::None {} => {
+                if optional_values.contains(&location) {
+                    let mutation = MutationType::ConstantReplacement{new_constant: Constant::Sentinel};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                }
+            },
+
+            ast::ExpressionType::String {value} => {
+                // Formatted strings are left alone
+                if let ast::StringGroup::Constant {value} = value {
+                    if !docstrings.contains(&location) {
+                        let mut replacements = vec![String::from("XXpymutXX")];
+                        if !value.is_empty() {
+                            replacements.push(String::new());
+                        }
+
+                        for new_constant in replacements {
+                            let mutation = MutationType::StringConstantReplacement{new_constant};
+                            mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
+                        }
+                    }
+                }
+            },
+
+            ast::ExpressionType::Bytes {value} => {
+                if !value.is_empty() {
+                    let mutation = MutationType::BytesConstantReplacement{new_constant: Vec::new()};
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
                 }
             },
 
@@ -114,41 +1388,63 @@ pub fn explore_mutations(program: &mut ast::Program) -> Vec<Mutation> {
 
                 {
                     let mutation = MutationType::NumberConstantReplacement{new_constant: plus_one(&value)};
-                    mutations.push(Mutation{traversal_location: i, mutation_type: mutation});
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
                 }
                 {
                     let mutation = MutationType::NumberConstantReplacement{new_constant: minus_one(&value)};
-                    mutations.push(Mutation{traversal_location: i, mutation_type: mutation});
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
                 }
                 {
                     let mutation = MutationType::NumberConstantReplacement{new_constant: from_i64(0)};
-                    mutations.push(Mutation{traversal_location: i, mutation_type: mutation});
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
                 }
                 {
                     let mutation = MutationType::NumberConstantReplacement{new_constant: from_i64(1)};
-                    mutations.push(Mutation{traversal_location: i, mutation_type: mutation});
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
                 }
                 {
                     let mutation = MutationType::NumberConstantReplacement{new_constant: from_i64(-1)};
-                    mutations.push(Mutation{traversal_location: i, mutation_type: mutation});
+                    mutations.push(Mutation{location: location.clone(), mutation_type: mutation});
                 }
             },
 
             _ => (),
         }
+
+        if ambiguous {
+            mutations.truncate(first_mutation);
+        } else {
+            seen_expressions.insert(location);
+        }
     });
 
+    // Nodes are skipped by the line they start on
+    let skipped = skipped_lines(source);
+    mutations.retain(|mutation| !skipped.contains(&mutation.location.line));
+
     return mutations;
 }
 
-pub fn apply_mutation(program: &mut ast::Program, mutation: Mutation) {
-    let mut i: u64 = 0;
+// Returns false if no node matches the location of the mutation, e.g. because it was explored by another version of pymut
+pub fn apply_mutation(program: &mut ast::Program, mutation: Mutation) -> bool {
+    // A mutated node may keep its position and kind, so only the first match is mutated
+    let mut applied = false;
 
-    program.visit(&mut |expr| {
-        i += 1;
+    if mutation.mutation_type.targets_statement() {
+        program.visit_statements(&mut |suite, index| {
+            if !applied && NodeLocation::of_statement(&suite[index]) == mutation.location {
+                suite.mutate_statement(index, mutation.mutation_type.clone());
+                applied = true;
+            }
+        });
+    } else {
+        program.visit(&mut |expr| {
+            if !applied && NodeLocation::of_expression(expr) == mutation.location {
+                expr.mutate(mutation.mutation_type.clone());
+                applied = true;
+            }
+        });
+    }
 
-        if i == mutation.traversal_location {
-            expr.mutate(mutation.mutation_type.clone());
-        }
-    });
+    applied
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+// Lines whose mutations are skipped, marked by comments in the source:
+//   `# pymut: skip` (or `# pragma: no mutate`) skips the line it is on
+//   `# pymut: off` skips every line up to and including the next `# pymut: on`, or the rest of the file
+pub fn skipped_lines(source: &str) -> HashSet<usize> {
+    let mut skipped: HashSet<usize> = HashSet::new();
+    let mut off = false;
+
+    // Lines are numbered from 1, like node locations
+    for (index, line) in source.lines().enumerate() {
+        let number = index + 1;
+
+        match directive(line) {
+            Some("off") => off = true,
+            Some("on") if off => {
+                off = false;
+                skipped.insert(number);
+            },
+            Some("skip") if !off => { skipped.insert(number); },
+            _ => (),
+        }
+
+        if off {
+            skipped.insert(number);
+        }
+    }
+
+    skipped
+}
+
+// A `#` inside a string literal is taken as the start of a comment as well, which only matters if the string looks like a pragma
+fn directive(line: &str) -> Option<&'static str> {
+    for (position, _) in line.match_indices('#') {
+        let comment = line[position + 1..].trim();
+
+        if comment.starts_with("pragma: no mutate") {
+            return Some("skip");
+        }
+
+        if comment.starts_with("pymut:") {
+            let word = comment["pymut:".len()..].split_whitespace().next().unwrap_or("");
+            match word {
+                "skip" => return Some("skip"),
+                "off" => return Some("off"),
+                "on" => return Some("on"),
+                _ => (),
+            }
+        }
+    }
+
+    None
+}
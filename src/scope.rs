@@ -0,0 +1,205 @@
+use std::collections::{HashMap, HashSet};
+use rustpython_parser::ast;
+
+use crate::traversal::{Visitor, StatementVisitor};
+use crate::mutation::NodeLocation;
+
+// For every read of a local name, the other local names which are bound at that point
+pub type ScopeTable = HashMap<NodeLocation, Vec<String>>;
+
+pub fn build_scope_table(program: &mut ast::Program) -> ScopeTable {
+    let mut table: ScopeTable = HashMap::new();
+
+    // Nested functions are visited as well, and are scopes of their own
+    program.visit_statements(&mut |suite, index| {
+        if let ast::StatementType::FunctionDef {is_async: _, name: _, args, body, decorator_list: _, returns: _} = &mut suite[index].node {
+            let mut bound: Vec<String> = Vec::new();
+
+            for parameter in args.args.iter().chain(args.kwonlyargs.iter()) {
+                bind(&parameter.arg, &mut bound);
+            }
+            for varargs in [&args.vararg, &args.kwarg] {
+                if let ast::Varargs::Named(parameter) = varargs {
+                    bind(&parameter.arg, &mut bound);
+                }
+            }
+
+            scan_suite(body, &mut bound, &mut table);
+        }
+    });
+
+    table
+}
+
+fn bind(name: &str, bound: &mut Vec<String>) {
+    if !bound.iter().any(|other| other == name) {
+        bound.push(String::from(name));
+    }
+}
+
+// Names bound by the end of every one of the paths, such as both branches of an if statement
+fn bound_on_every_path(paths: Vec<Vec<String>>) -> Vec<String> {
+    let mut paths = paths.into_iter();
+    let mut bound = paths.next().unwrap_or_default();
+
+    for path in paths {
+        bound.retain(|name| path.contains(name));
+    }
+
+    bound
+}
+
+fn record_reads<T: Visitor>(visitable: &mut T, bound: &[String], table: &mut ScopeTable) {
+    // Names bound by a comprehension, such as the target of `for a in b` within `[a for a in b]`, are visited after the comprehension itself
+    let mut comprehension_targets: HashSet<NodeLocation> = HashSet::new();
+
+    visitable.visit(&mut |expr| {
+        if let ast::ExpressionType::Comprehension {kind: _, generators} = &mut expr.node {
+            for generator in generators {
+                generator.target.visit(&mut |target| { comprehension_targets.insert(NodeLocation::of_expression(target)); });
+            }
+        }
+
+        if let ast::ExpressionType::Identifier {name} = &expr.node {
+            if bound.contains(name) && !comprehension_targets.contains(&NodeLocation::of_expression(expr)) {
+                let others: Vec<String> = bound.iter().filter(|other| *other != name).cloned().collect();
+
+                if !others.is_empty() {
+                    table.insert(NodeLocation::of_expression(expr), others);
+                }
+            }
+        }
+    });
+}
+
+fn bind_target(target: &mut ast::Expression, bound: &mut Vec<String>, table: &mut ScopeTable) {
+    match &mut target.node {
+        ast::ExpressionType::Identifier {name} => bind(name, bound),
+        ast::ExpressionType::Tuple {elements} => for element in elements { bind_target(element, bound, table) },
+        ast::ExpressionType::List {elements} => for element in elements { bind_target(element, bound, table) },
+        ast::ExpressionType::Starred {value} => bind_target(value, bound, table),
+        // Such as `a[i] = x` or `a.b = x`, which read `a` and `i`
+        _ => record_reads(target, bound, table),
+    }
+}
+
+// The name of `except ... as e` is deleted at the end of the handler
+fn scan_handler(handler: &mut ast::ExceptHandler, bound: &mut Vec<String>, table: &mut ScopeTable) {
+    match &handler.name {
+        // Even if the name was bound before the handler
+        Some(name) => {
+            bind(name, bound);
+            scan_suite(&mut handler.body, bound, table);
+            bound.retain(|other| other != name);
+        },
+        None => scan_suite(&mut handler.body, bound, table),
+    }
+}
+
+// Statements are scanned in source order, so a name counts as bound after its first assignment
+fn scan_suite(suite: &mut [ast::Statement], bound: &mut Vec<String>, table: &mut ScopeTable) {
+    for statement in suite.iter_mut() {
+        match &mut statement.node {
+            // Bodies of nested definitions are separate scopes
+            ast::StatementType::FunctionDef {is_async: _, name, args: _, body: _, decorator_list: _, returns: _} => bind(name, bound),
+            ast::StatementType::ClassDef {name, body: _, bases: _, keywords: _, decorator_list: _} => bind(name, bound),
+
+            ast::StatementType::Assign {targets, value} => {
+                record_reads(value, bound, table);
+                for target in targets {
+                    bind_target(target, bound, table);
+                }
+            },
+
+            // The target is read as well, but replacing it would also change what is written
+            ast::StatementType::AugAssign {target: _, op: _, value} => record_reads(value, bound, table),
+
+            ast::StatementType::AnnAssign {target, annotation: _, value} => {
+                record_reads(value, bound, table);
+                bind_target(target, bound, table);
+            },
+
+            // Each branch starts from the names bound before it, and only names bound in both count afterwards
+            ast::StatementType::If {test, body, orelse} => {
+                record_reads(test, bound, table);
+
+                let mut body_bound = bound.clone();
+                scan_suite(body, &mut body_bound, table);
+
+                let mut orelse_bound = bound.clone();
+                if let Some(orelse) = orelse {
+                    scan_suite(orelse, &mut orelse_bound, table);
+                }
+
+                *bound = bound_on_every_path(vec![body_bound, orelse_bound]);
+            },
+
+            // The body may run no times and the else clause is skipped by `break`, so neither binds anything afterwards
+            ast::StatementType::While {test, body, orelse} => {
+                record_reads(test, bound, table);
+                scan_suite(body, &mut bound.clone(), table);
+                if let Some(orelse) = orelse {
+                    scan_suite(orelse, &mut bound.clone(), table);
+                }
+            },
+
+            ast::StatementType::For {is_async: _, target, iter, body, orelse} => {
+                record_reads(iter, bound, table);
+
+                let mut body_bound = bound.clone();
+                bind_target(target, &mut body_bound, table);
+                scan_suite(body, &mut body_bound, table);
+
+                if let Some(orelse) = orelse {
+                    scan_suite(orelse, &mut bound.clone(), table);
+                }
+            },
+
+            ast::StatementType::With {is_async: _, items, body} => {
+                for item in items {
+                    record_reads(&mut item.context_expr, bound, table);
+                    if let Some(optional_vars) = &mut item.optional_vars {
+                        bind_target(optional_vars, bound, table);
+                    }
+                }
+                scan_suite(body, bound, table);
+            },
+
+            // A handler or `finally` clause may run after any part of the body, so they start from the names bound before it
+            ast::StatementType::Try {body, handlers, orelse, finalbody} => {
+                let mut paths: Vec<Vec<String>> = Vec::new();
+
+                let mut body_bound = bound.clone();
+                scan_suite(body, &mut body_bound, table);
+                if let Some(orelse) = orelse {
+                    scan_suite(orelse, &mut body_bound, table);
+                }
+                paths.push(body_bound);
+
+                for handler in handlers {
+                    let mut handler_bound = bound.clone();
+                    record_reads(&mut handler.typ, &handler_bound, table);
+                    scan_handler(handler, &mut handler_bound, table);
+                    paths.push(handler_bound);
+                }
+
+                let mut finally_bound = bound.clone();
+                if let Some(finalbody) = finalbody {
+                    scan_suite(finalbody, &mut finally_bound, table);
+                }
+
+                *bound = bound_on_every_path(paths);
+                for name in &finally_bound {
+                    bind(name, bound);
+                }
+            },
+
+            // Names of `del`, `global` and `nonlocal` statements are not reads
+            ast::StatementType::Delete {targets: _} => (),
+            ast::StatementType::Global {names: _} => (),
+            ast::StatementType::Nonlocal {names: _} => (),
+
+            _ => record_reads(statement, bound, table),
+        }
+    }
+}
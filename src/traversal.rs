@@ -170,4 +170,136 @@ impl Visitor for ast::Statement {
             ast::StatementType::FunctionDef {is_async: _, name: _, args, body, decorator_list, returns} => { args.visit(callback); body.visit(callback); decorator_list.visit(callback); returns.visit(callback); },
         }
     }
-}
\ No newline at end of file
+}
+
+pub trait StatementVisitor {
+    fn visit_statements(&mut self, callback: &mut dyn FnMut(&mut Vec<ast::Statement>, usize));
+}
+
+impl StatementVisitor for ast::Program {
+    fn visit_statements(&mut self, callback: &mut dyn FnMut(&mut Vec<ast::Statement>, usize)) {
+        self.statements.visit_statements(callback);
+    }
+}
+
+impl<T: StatementVisitor> StatementVisitor for Option<T> {
+    fn visit_statements(&mut self, callback: &mut dyn FnMut(&mut Vec<ast::Statement>, usize)) {
+        match self {
+            Some(visitable) => visitable.visit_statements(callback),
+            None => (),
+        }
+    }
+}
+
+// Statements are yielded together with their enclosing block so that callbacks can remove them
+impl StatementVisitor for Vec<ast::Statement> {
+    fn visit_statements(&mut self, callback: &mut dyn FnMut(&mut Vec<ast::Statement>, usize)) {
+        let mut index = 0;
+
+        while index < self.len() {
+            callback(self, index);
+
+            // The callback may have removed the statement
+            if index < self.len() {
+                self[index].visit_statements(callback);
+            }
+
+            index += 1;
+        }
+    }
+}
+
+impl StatementVisitor for ast::Statement {
+    fn visit_statements(&mut self, callback: &mut dyn FnMut(&mut Vec<ast::Statement>, usize)) {
+        match &mut self.node {
+            ast::StatementType::Break => (),
+            ast::StatementType::Continue => (),
+            ast::StatementType::Return {value: _} => (),
+            ast::StatementType::Import {names: _} => (),
+            ast::StatementType::ImportFrom {level: _, module: _, names: _} => (),
+            ast::StatementType::Pass => (),
+            ast::StatementType::Assert {test: _, msg: _} => (),
+            ast::StatementType::Delete {targets: _} => (),
+            ast::StatementType::Assign {targets: _, value: _} => (),
+            ast::StatementType::AugAssign {target: _, op: _, value: _} => (),
+            ast::StatementType::AnnAssign {target: _, annotation: _, value: _} => (),
+            ast::StatementType::Expression {expression: _} => (),
+            ast::StatementType::Global {names: _} => (),
+            ast::StatementType::Nonlocal {names: _} => (),
+            ast::StatementType::If {test: _, body, orelse} => { body.visit_statements(callback); orelse.visit_statements(callback); },
+            ast::StatementType::While {test: _, body, orelse} => { body.visit_statements(callback); orelse.visit_statements(callback); },
+            ast::StatementType::With {is_async: _, items: _, body} => body.visit_statements(callback),
+            ast::StatementType::For {is_async: _, target: _, iter: _, body, orelse} => { body.visit_statements(callback); orelse.visit_statements(callback); },
+            ast::StatementType::Raise {exception: _, cause: _} => (),
+            ast::StatementType::Try {body, handlers, orelse, finalbody} => {
+                body.visit_statements(callback);
+                for handler in handlers {
+                    handler.body.visit_statements(callback);
+                }
+                orelse.visit_statements(callback);
+                finalbody.visit_statements(callback);
+            },
+            ast::StatementType::ClassDef {name: _, body, bases: _, keywords: _, decorator_list: _} => body.visit_statements(callback),
+            ast::StatementType::FunctionDef {is_async: _, name: _, args: _, body, decorator_list: _, returns: _} => body.visit_statements(callback),
+        }
+    }
+}
+
+pub fn expression_kind(expression: &ast::ExpressionType) -> &'static str {
+    match expression {
+        ast::ExpressionType::BoolOp {..} => "BoolOp",
+        ast::ExpressionType::Binop {..} => "Binop",
+        ast::ExpressionType::Subscript {..} => "Subscript",
+        ast::ExpressionType::Unop {..} => "Unop",
+        ast::ExpressionType::Await {..} => "Await",
+        ast::ExpressionType::Yield {..} => "Yield",
+        ast::ExpressionType::YieldFrom {..} => "YieldFrom",
+        ast::ExpressionType::Compare {..} => "Compare",
+        ast::ExpressionType::Attribute {..} => "Attribute",
+        ast::ExpressionType::Call {..} => "Call",
+        ast::ExpressionType::Number {..} => "Number",
+        ast::ExpressionType::List {..} => "List",
+        ast::ExpressionType::Tuple {..} => "Tuple",
+        ast::ExpressionType::Dict {..} => "Dict",
+        ast::ExpressionType::Set {..} => "Set",
+        ast::ExpressionType::Comprehension {..} => "Comprehension",
+        ast::ExpressionType::Starred {..} => "Starred",
+        ast::ExpressionType::Slice {..} => "Slice",
+        ast::ExpressionType::String {..} => "String",
+        ast::ExpressionType::Bytes {..} => "Bytes",
+        ast::ExpressionType::Identifier {..} => "Identifier",
+        ast::ExpressionType::Lambda {..} => "Lambda",
+        ast::ExpressionType::IfExpression {..} => "IfExpression",
+        ast::ExpressionType::True {} => "True",
+        ast::ExpressionType::False {} => "False",
+        ast::ExpressionType::None {} => "None",
+        ast::ExpressionType::Ellipsis {} => "Ellipsis",
+    }
+}
+
+pub fn statement_kind(statement: &ast::StatementType) -> &'static str {
+    match statement {
+        ast::StatementType::Break => "Break",
+        ast::StatementType::Continue => "Continue",
+        ast::StatementType::Return {..} => "Return",
+        ast::StatementType::Import {..} => "Import",
+        ast::StatementType::ImportFrom {..} => "ImportFrom",
+        ast::StatementType::Pass => "Pass",
+        ast::StatementType::Assert {..} => "Assert",
+        ast::StatementType::Delete {..} => "Delete",
+        ast::StatementType::Assign {..} => "Assign",
+        ast::StatementType::AugAssign {..} => "AugAssign",
+        ast::StatementType::AnnAssign {..} => "AnnAssign",
+        ast::StatementType::Expression {..} => "Expression",
+        ast::StatementType::Global {..} => "Global",
+        ast::StatementType::Nonlocal {..} => "Nonlocal",
+        ast::StatementType::If {..} => "If",
+        ast::StatementType::While {..} => "While",
+        ast::StatementType::With {..} => "With",
+        ast::StatementType::For {..} => "For",
+        ast::StatementType::Raise {..} => "Raise",
+        ast::StatementType::Try {..} => "Try",
+        ast::StatementType::ClassDef {..} => "ClassDef",
+        ast::StatementType::FunctionDef {..} => "FunctionDef",
+    }
+}
@@ -0,0 +1,531 @@
+use rustpython_parser::ast;
+
+// Prints a program back to Python source. Comments and the original formatting are not preserved.
+pub fn unparse_program(program: &ast::Program) -> String {
+    let mut output = String::new();
+    unparse_suite(&program.statements, 0, &mut output);
+    output
+}
+
+fn push_line(output: &mut String, depth: usize, line: &str) {
+    output.push_str(&"    ".repeat(depth));
+    output.push_str(line);
+    output.push('\n');
+}
+
+fn unparse_suite(suite: &[ast::Statement], depth: usize, output: &mut String) {
+    if suite.is_empty() {
+        push_line(output, depth, "pass");
+    }
+
+    for statement in suite {
+        unparse_statement(statement, depth, output);
+    }
+}
+
+fn unparse_block(header: &str, suite: &[ast::Statement], depth: usize, output: &mut String) {
+    push_line(output, depth, &format!("{}:", header));
+    unparse_suite(suite, depth + 1, output);
+}
+
+fn unparse_orelse(orelse: &Option<Vec<ast::Statement>>, depth: usize, output: &mut String) {
+    if let Some(orelse) = orelse {
+        unparse_block("else", orelse, depth, output);
+    }
+}
+
+fn unparse_statement(statement: &ast::Statement, depth: usize, output: &mut String) {
+    match &statement.node {
+        ast::StatementType::Break => push_line(output, depth, "break"),
+        ast::StatementType::Continue => push_line(output, depth, "continue"),
+        ast::StatementType::Return {value} => {
+            match value {
+                Some(value) => push_line(output, depth, &format!("return {}", operand(value, 1))),
+                None => push_line(output, depth, "return"),
+            }
+        },
+        ast::StatementType::Import {names} => {
+            let names: Vec<String> = names.iter().map(import_symbol).collect();
+            push_line(output, depth, &format!("import {}", names.join(", ")));
+        },
+        ast::StatementType::ImportFrom {level, module, names} => {
+            let module = format!("{}{}", ".".repeat(*level), module.as_deref().unwrap_or(""));
+            let names: Vec<String> = names.iter().map(import_symbol).collect();
+            push_line(output, depth, &format!("from {} import {}", module, names.join(", ")));
+        },
+        ast::StatementType::Pass => push_line(output, depth, "pass"),
+        ast::StatementType::Assert {test, msg} => {
+            match msg {
+                Some(msg) => push_line(output, depth, &format!("assert {}, {}", operand(test, 1), operand(msg, 1))),
+                None => push_line(output, depth, &format!("assert {}", operand(test, 1))),
+            }
+        },
+        ast::StatementType::Delete {targets} => {
+            push_line(output, depth, &format!("del {}", operands(targets, 1)));
+        },
+        ast::StatementType::Assign {targets, value} => {
+            let mut line = String::new();
+            for target in targets {
+                line.push_str(&format!("{} = ", operand(target, 1)));
+            }
+            line.push_str(&operand(value, 0));
+            push_line(output, depth, &line);
+        },
+        ast::StatementType::AugAssign {target, op, value} => {
+            push_line(output, depth, &format!("{} {}= {}", operand(target, 1), operator(op), operand(value, 0)));
+        },
+        ast::StatementType::AnnAssign {target, annotation, value} => {
+            match value {
+                Some(value) => push_line(output, depth, &format!("{}: {} = {}", operand(target, 1), operand(annotation, 1), operand(value, 0))),
+                None => push_line(output, depth, &format!("{}: {}", operand(target, 1), operand(annotation, 1))),
+            }
+        },
+        ast::StatementType::Expression {expression} => push_line(output, depth, &operand(expression, 0)),
+        ast::StatementType::Global {names} => push_line(output, depth, &format!("global {}", names.join(", "))),
+        ast::StatementType::Nonlocal {names} => push_line(output, depth, &format!("nonlocal {}", names.join(", "))),
+        ast::StatementType::If {test, body, orelse} => {
+            unparse_block(&format!("if {}", operand(test, 1)), body, depth, output);
+            unparse_elif(orelse, depth, output);
+        },
+        ast::StatementType::While {test, body, orelse} => {
+            unparse_block(&format!("while {}", operand(test, 1)), body, depth, output);
+            unparse_orelse(orelse, depth, output);
+        },
+        ast::StatementType::With {is_async, items, body} => {
+            let items: Vec<String> = items.iter().map(|item| {
+                match &item.optional_vars {
+                    Some(optional_vars) => format!("{} as {}", operand(&item.context_expr, 1), operand(optional_vars, 7)),
+                    None => operand(&item.context_expr, 1),
+                }
+            }).collect();
+            let keyword = if *is_async { "async with" } else { "with" };
+            unparse_block(&format!("{} {}", keyword, items.join(", ")), body, depth, output);
+        },
+        ast::StatementType::For {is_async, target, iter, body, orelse} => {
+            let keyword = if *is_async { "async for" } else { "for" };
+            unparse_block(&format!("{} {} in {}", keyword, operand(target, 7), operand(iter, 1)), body, depth, output);
+            unparse_orelse(orelse, depth, output);
+        },
+        ast::StatementType::Raise {exception, cause} => {
+            match (exception, cause) {
+                (Some(exception), Some(cause)) => push_line(output, depth, &format!("raise {} from {}", operand(exception, 1), operand(cause, 1))),
+                (Some(exception), None) => push_line(output, depth, &format!("raise {}", operand(exception, 1))),
+                _ => push_line(output, depth, "raise"),
+            }
+        },
+        ast::StatementType::Try {body, handlers, orelse, finalbody} => {
+            unparse_block("try", body, depth, output);
+            for handler in handlers {
+                let header = match (&handler.typ, &handler.name) {
+                    (Some(typ), Some(name)) => format!("except {} as {}", operand(typ, 1), name),
+                    (Some(typ), None) => format!("except {}", operand(typ, 1)),
+                    _ => String::from("except"),
+                };
+                unparse_block(&header, &handler.body, depth, output);
+            }
+            unparse_orelse(orelse, depth, output);
+            if let Some(finalbody) = finalbody {
+                unparse_block("finally", finalbody, depth, output);
+            }
+        },
+        ast::StatementType::ClassDef {name, body, bases, keywords, decorator_list} => {
+            for decorator in decorator_list {
+                push_line(output, depth, &format!("@{}", operand(decorator, 1)));
+            }
+            let mut arguments: Vec<String> = bases.iter().map(|base| operand(base, 1)).collect();
+            arguments.extend(keywords.iter().map(keyword));
+            if arguments.is_empty() {
+                unparse_block(&format!("class {}", name), body, depth, output);
+            } else {
+                unparse_block(&format!("class {}({})", name, arguments.join(", ")), body, depth, output);
+            }
+        },
+        ast::StatementType::FunctionDef {is_async, name, args, body, decorator_list, returns} => {
+            for decorator in decorator_list {
+                push_line(output, depth, &format!("@{}", operand(decorator, 1)));
+            }
+            let keyword = if *is_async { "async def" } else { "def" };
+            let mut header = format!("{} {}({})", keyword, name, parameters(args, true));
+            if let Some(returns) = returns {
+                header.push_str(&format!(" -> {}", operand(returns, 1)));
+            }
+            unparse_block(&header, body, depth, output);
+        },
+    }
+}
+
+// An else block holding a single if statement is printed as `elif`
+fn unparse_elif(orelse: &Option<Vec<ast::Statement>>, depth: usize, output: &mut String) {
+    if let Some(orelse) = orelse {
+        if let [statement] = orelse.as_slice() {
+            if let ast::StatementType::If {test, body, orelse} = &statement.node {
+                unparse_block(&format!("elif {}", operand(test, 1)), body, depth, output);
+                unparse_elif(orelse, depth, output);
+                return;
+            }
+        }
+
+        unparse_block("else", orelse, depth, output);
+    }
+}
+
+fn import_symbol(symbol: &ast::ImportSymbol) -> String {
+    match &symbol.alias {
+        Some(alias) => format!("{} as {}", symbol.symbol, alias),
+        None => symbol.symbol.clone(),
+    }
+}
+
+fn keyword(keyword: &ast::Keyword) -> String {
+    match &keyword.name {
+        Some(name) => format!("{}={}", name, operand(&keyword.value, 1)),
+        None => format!("**{}", operand(&keyword.value, 7)),
+    }
+}
+
+fn parameter(parameter: &ast::Parameter, annotations: bool) -> String {
+    match &parameter.annotation {
+        Some(annotation) if annotations => format!("{}: {}", parameter.arg, operand(annotation, 1)),
+        _ => parameter.arg.clone(),
+    }
+}
+
+fn parameters(parameters: &ast::Parameters, annotations: bool) -> String {
+    let mut printed: Vec<String> = Vec::new();
+
+    // Defaults belong to the last positional parameters
+    let first_default = parameters.args.len() - parameters.defaults.len();
+    for (index, argument) in parameters.args.iter().enumerate() {
+        if index < first_default {
+            printed.push(parameter(argument, annotations));
+        } else {
+            printed.push(format!("{}={}", parameter(argument, annotations), operand(&parameters.defaults[index - first_default], 1)));
+        }
+    }
+
+    match &parameters.vararg {
+        ast::Varargs::Named(vararg) => printed.push(format!("*{}", parameter(vararg, annotations))),
+        ast::Varargs::Unnamed => printed.push(String::from("*")),
+        ast::Varargs::None => {
+            if !parameters.kwonlyargs.is_empty() {
+                printed.push(String::from("*"));
+            }
+        },
+    }
+
+    for (index, argument) in parameters.kwonlyargs.iter().enumerate() {
+        match parameters.kw_defaults.get(index) {
+            Some(Some(default)) => printed.push(format!("{}={}", parameter(argument, annotations), operand(default, 1))),
+            _ => printed.push(parameter(argument, annotations)),
+        }
+    }
+
+    if let ast::Varargs::Named(kwarg) = &parameters.kwarg {
+        printed.push(format!("**{}", parameter(kwarg, annotations)));
+    }
+
+    printed.join(", ")
+}
+
+fn operator(op: &ast::Operator) -> &'static str {
+    match op {
+        ast::Operator::Add => "+",
+        ast::Operator::Sub => "-",
+        ast::Operator::Mult => "*",
+        ast::Operator::MatMult => "@",
+        ast::Operator::Div => "/",
+        ast::Operator::Mod => "%",
+        ast::Operator::Pow => "**",
+        ast::Operator::LShift => "<<",
+        ast::Operator::RShift => ">>",
+        ast::Operator::BitOr => "|",
+        ast::Operator::BitXor => "^",
+        ast::Operator::BitAnd => "&",
+        ast::Operator::FloorDiv => "//",
+    }
+}
+
+fn comparison(op: &ast::Comparison) -> &'static str {
+    match op {
+        ast::Comparison::Equal => "==",
+        ast::Comparison::NotEqual => "!=",
+        ast::Comparison::Less => "<",
+        ast::Comparison::LessOrEqual => "<=",
+        ast::Comparison::Greater => ">",
+        ast::Comparison::GreaterOrEqual => ">=",
+        ast::Comparison::In => "in",
+        ast::Comparison::NotIn => "not in",
+        ast::Comparison::Is => "is",
+        ast::Comparison::IsNot => "is not",
+    }
+}
+
+// How tightly an expression binds, following the Python grammar. Atoms bind the tightest.
+fn precedence(expression: &ast::Expression) -> u8 {
+    match &expression.node {
+        ast::ExpressionType::Yield {..} => 0,
+        ast::ExpressionType::YieldFrom {..} => 0,
+        ast::ExpressionType::Lambda {..} => 1,
+        ast::ExpressionType::IfExpression {..} => 2,
+        ast::ExpressionType::BoolOp {op: ast::BooleanOperator::Or, values: _} => 3,
+        ast::ExpressionType::BoolOp {op: ast::BooleanOperator::And, values: _} => 4,
+        ast::ExpressionType::Unop {op: ast::UnaryOperator::Not, a: _} => 5,
+        ast::ExpressionType::Compare {..} => 6,
+        ast::ExpressionType::Binop {a: _, op, b: _} => {
+            match op {
+                ast::Operator::BitOr => 7,
+                ast::Operator::BitXor => 8,
+                ast::Operator::BitAnd => 9,
+                ast::Operator::LShift | ast::Operator::RShift => 10,
+                ast::Operator::Add | ast::Operator::Sub => 11,
+                ast::Operator::Mult | ast::Operator::MatMult | ast::Operator::Div | ast::Operator::FloorDiv | ast::Operator::Mod => 12,
+                ast::Operator::Pow => 14,
+            }
+        },
+        ast::ExpressionType::Unop {..} => 13,
+        // Printed with a leading minus sign
+        ast::ExpressionType::Number {value: ast::Number::Integer {value}} if value.sign() == num_bigint::Sign::Minus => 13,
+        ast::ExpressionType::Number {value: ast::Number::Float {value}} if value.is_sign_negative() => 13,
+        ast::ExpressionType::Await {..} => 15,
+        _ => 16,
+    }
+}
+
+// Prints an expression, parenthesized if it binds less tightly than required by its context
+fn operand(expression: &ast::Expression, required: u8) -> String {
+    let printed = unparse_expression(expression);
+
+    if precedence(expression) < required {
+        format!("({})", printed)
+    } else {
+        printed
+    }
+}
+
+fn operands(expressions: &[ast::Expression], required: u8) -> String {
+    let printed: Vec<String> = expressions.iter().map(|expression| operand(expression, required)).collect();
+    printed.join(", ")
+}
+
+pub fn unparse_expression(expression: &ast::Expression) -> String {
+    let own = precedence(expression);
+
+    match &expression.node {
+        ast::ExpressionType::BoolOp {op, values} => {
+            let separator = match op {
+                ast::BooleanOperator::And => " and ",
+                ast::BooleanOperator::Or => " or ",
+            };
+            let printed: Vec<String> = values.iter().map(|value| operand(value, own + 1)).collect();
+            printed.join(separator)
+        },
+        ast::ExpressionType::Binop {a, op: ast::Operator::Pow, b} => {
+            // Right associative, and binds tighter than a unary operator on its left
+            format!("{} ** {}", operand(a, 15), operand(b, 13))
+        },
+        ast::ExpressionType::Binop {a, op, b} => format!("{} {} {}", operand(a, own), operator(op), operand(b, own + 1)),
+        ast::ExpressionType::Subscript {a, b} => {
+            let index = match &b.node {
+                // Without parentheses, so that slices may be elements, e.g. `a[1:2, j]`
+                ast::ExpressionType::Tuple {elements} if elements.len() == 1 => format!("{},", operand(&elements[0], 1)),
+                ast::ExpressionType::Tuple {elements} if !elements.is_empty() => operands(elements, 1),
+                _ => operand(b, 1),
+            };
+            format!("{}[{}]", operand(a, 16), index)
+        },
+        ast::ExpressionType::Unop {op, a} => {
+            match op {
+                ast::UnaryOperator::Not => format!("not {}", operand(a, own)),
+                ast::UnaryOperator::Neg => format!("-{}", operand(a, own)),
+                ast::UnaryOperator::Pos => format!("+{}", operand(a, own)),
+                ast::UnaryOperator::Inv => format!("~{}", operand(a, own)),
+            }
+        },
+        ast::ExpressionType::Await {value} => format!("await {}", operand(value, 16)),
+        ast::ExpressionType::Yield {value} => {
+            match value {
+                Some(value) => format!("yield {}", operand(value, 1)),
+                None => String::from("yield"),
+            }
+        },
+        ast::ExpressionType::YieldFrom {value} => format!("yield from {}", operand(value, 1)),
+        ast::ExpressionType::Compare {vals, ops} => {
+            let mut printed = operand(&vals[0], 7);
+            for (op, val) in ops.iter().zip(vals.iter().skip(1)) {
+                printed.push_str(&format!(" {} {}", comparison(op), operand(val, 7)));
+            }
+            printed
+        },
+        ast::ExpressionType::Attribute {value, name} => {
+            // `1.real` would be read as a float
+            match &value.node {
+                ast::ExpressionType::Number {value: _} => format!("({}).{}", unparse_expression(value), name),
+                _ => format!("{}.{}", operand(value, 16), name),
+            }
+        },
+        ast::ExpressionType::Call {function, args, keywords} => {
+            let mut arguments: Vec<String> = args.iter().map(|arg| operand(arg, 1)).collect();
+            arguments.extend(keywords.iter().map(keyword));
+            format!("{}({})", operand(function, 16), arguments.join(", "))
+        },
+        ast::ExpressionType::Number {value} => number(value),
+        ast::ExpressionType::List {elements} => format!("[{}]", operands(elements, 1)),
+        ast::ExpressionType::Tuple {elements} => {
+            match elements.len() {
+                1 => format!("({},)", operand(&elements[0], 1)),
+                _ => format!("({})", operands(elements, 1)),
+            }
+        },
+        ast::ExpressionType::Dict {elements} => {
+            let printed: Vec<String> = elements.iter().map(|(key, value)| {
+                match key {
+                    Some(key) => format!("{}: {}", operand(key, 1), operand(value, 1)),
+                    None => format!("**{}", operand(value, 7)),
+                }
+            }).collect();
+            format!("{{{}}}", printed.join(", "))
+        },
+        ast::ExpressionType::Set {elements} => format!("{{{}}}", operands(elements, 1)),
+        ast::ExpressionType::Comprehension {kind, generators} => {
+            let mut clauses = String::new();
+            for generator in generators {
+                clauses.push_str(&format!(" for {} in {}", operand(&generator.target, 7), operand(&generator.iter, 3)));
+                for filter in &generator.ifs {
+                    clauses.push_str(&format!(" if {}", operand(filter, 3)));
+                }
+            }
+
+            match &**kind {
+                ast::ComprehensionKind::GeneratorExpression {element} => format!("({}{})", operand(element, 1), clauses),
+                ast::ComprehensionKind::List {element} => format!("[{}{}]", operand(element, 1), clauses),
+                ast::ComprehensionKind::Set {element} => format!("{{{}{}}}", operand(element, 1), clauses),
+                ast::ComprehensionKind::Dict {key, value} => format!("{{{}: {}{}}}", operand(key, 1), operand(value, 1), clauses),
+            }
+        },
+        ast::ExpressionType::Starred {value} => format!("*{}", operand(value, 7)),
+        ast::ExpressionType::Slice {elements} => {
+            let bound = |index: usize| {
+                match elements.get(index) {
+                    Some(element) if !matches!(element.node, ast::ExpressionType::None {}) => operand(element, 1),
+                    _ => String::new(),
+                }
+            };
+
+            match elements.get(2) {
+                Some(step) if !matches!(step.node, ast::ExpressionType::None {}) => format!("{}:{}:{}", bound(0), bound(1), bound(2)),
+                _ => format!("{}:{}", bound(0), bound(1)),
+            }
+        },
+        ast::ExpressionType::String {value} => {
+            match value {
+                ast::StringGroup::Constant {value} => string_literal(value),
+                _ => format!("f{}", string_literal(&formatted_string(value))),
+            }
+        },
+        ast::ExpressionType::Bytes {value} => bytes_literal(value),
+        ast::ExpressionType::Identifier {name} => name.clone(),
+        ast::ExpressionType::Lambda {args, body} => {
+            let printed = parameters(args, false);
+            if printed.is_empty() {
+                format!("lambda: {}", operand(body, 1))
+            } else {
+                format!("lambda {}: {}", printed, operand(body, 1))
+            }
+        },
+        ast::ExpressionType::IfExpression {test, body, orelse} => {
+            format!("{} if {} else {}", operand(body, 3), operand(test, 3), operand(orelse, 2))
+        },
+        ast::ExpressionType::True {} => String::from("True"),
+        ast::ExpressionType::False {} => String::from("False"),
+        ast::ExpressionType::None {} => String::from("None"),
+        ast::ExpressionType::Ellipsis {} => String::from("..."),
+    }
+}
+
+fn number(value: &ast::Number) -> String {
+    fn float(value: f64) -> String {
+        if value.is_infinite() {
+            // Too large for a float, which Python reads as infinity
+            String::from(if value < 0.0 { "-1e999" } else { "1e999" })
+        } else {
+            // Debug formatting always includes a decimal point or exponent
+            format!("{:?}", value)
+        }
+    }
+
+    match value {
+        ast::Number::Integer {value} => value.to_string(),
+        ast::Number::Float {value} => float(*value),
+        ast::Number::Complex {real, imag} => {
+            if *real == 0.0 {
+                format!("{}j", float(*imag))
+            } else {
+                format!("({} + {}j)", float(*real), float(*imag))
+            }
+        },
+    }
+}
+
+// The body of an f-string, before quoting
+fn formatted_string(group: &ast::StringGroup) -> String {
+    match group {
+        ast::StringGroup::Constant {value} => value.replace('{', "{{").replace('}', "}}"),
+        ast::StringGroup::FormattedValue {value, conversion, spec} => {
+            let mut printed = format!("{{{}", operand(value, 1));
+            match conversion {
+                Some(ast::ConversionFlag::Str) => printed.push_str("!s"),
+                Some(ast::ConversionFlag::Ascii) => printed.push_str("!a"),
+                Some(ast::ConversionFlag::Repr) => printed.push_str("!r"),
+                None => (),
+            }
+            if let Some(spec) = spec {
+                printed.push(':');
+                printed.push_str(&formatted_string(spec));
+            }
+            printed.push('}');
+            printed
+        },
+        ast::StringGroup::Joined {values} => values.iter().map(formatted_string).collect(),
+    }
+}
+
+fn string_literal(value: &str) -> String {
+    // Same choice of quotes as Python's repr
+    let quote = if value.contains('\'') && !value.contains('"') { '"' } else { '\'' };
+
+    let mut printed = String::new();
+    printed.push(quote);
+    for character in value.chars() {
+        match character {
+            '\\' => printed.push_str("\\\\"),
+            '\n' => printed.push_str("\\n"),
+            '\r' => printed.push_str("\\r"),
+            '\t' => printed.push_str("\\t"),
+            _ if character == quote => {
+                printed.push('\\');
+                printed.push(character);
+            },
+            _ if character.is_control() => printed.push_str(&format!("\\u{:04x}", character as u32)),
+            _ => printed.push(character),
+        }
+    }
+    printed.push(quote);
+    printed
+}
+
+fn bytes_literal(value: &[u8]) -> String {
+    let mut printed = String::from("b'");
+    for byte in value {
+        match byte {
+            b'\\' => printed.push_str("\\\\"),
+            b'\'' => printed.push_str("\\'"),
+            b'\n' => printed.push_str("\\n"),
+            b'\r' => printed.push_str("\\r"),
+            b'\t' => printed.push_str("\\t"),
+            0x20..=0x7e => printed.push(*byte as char),
+            _ => printed.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    printed.push('\'');
+    printed
+}
@@ -0,0 +1,4 @@
+import bounds
+
+assert bounds.in_range(0, 5, 10) == True
+assert bounds.is_positive(7) == True
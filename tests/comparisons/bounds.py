@@ -0,0 +1,8 @@
+def in_range(low, value, high):
+    return low <= value < high
+
+def is_positive(a):
+    return a > 0
+
+def contains(items, item):
+    return item in items
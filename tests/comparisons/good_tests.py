@@ -0,0 +1,9 @@
+import bounds
+
+assert bounds.in_range(0, 0, 10) == True
+assert bounds.in_range(0, 10, 10) == False
+assert bounds.in_range(0, 5, 10) == True
+assert bounds.is_positive(1) == True
+assert bounds.is_positive(0) == False
+assert bounds.contains([1, 2], 2) == True
+assert bounds.contains([1, 2], 3) == False
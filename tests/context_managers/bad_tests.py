@@ -0,0 +1,3 @@
+from transactions import transfer
+
+transfer([], 5)
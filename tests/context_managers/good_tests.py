@@ -0,0 +1,4 @@
+from transactions import transfer
+
+assert transfer([], 5) == ["begin", 5, "commit"]
+assert transfer([], 0) == ["begin", "rollback"]
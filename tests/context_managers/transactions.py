@@ -0,0 +1,22 @@
+class Transaction:
+    def __init__(self, journal):
+        self.journal = journal
+
+    def __enter__(self):
+        self.journal.append("begin")
+        return self
+
+    def __exit__(self, kind, value, traceback):
+        if kind is None:
+            self.journal.append("commit")
+        else:
+            self.journal.append("rollback")
+        return True
+
+
+def transfer(journal, amount):
+    with Transaction(journal):
+        if amount <= 0:
+            raise ValueError("amount must be positive")
+        journal.append(amount)
+    return journal
@@ -0,0 +1,3 @@
+from pricing import price
+
+price(10, 0.25)
@@ -0,0 +1,4 @@
+from pricing import price
+
+assert price(10, 0.25) == 25
+assert price(3, 0.1) == 7
@@ -0,0 +1,16 @@
+def doubled(function):
+    def wrapper(*args, **kwargs):
+        return 2 * function(*args, **kwargs)
+    return wrapper
+
+
+def rounded(function):
+    def wrapper(*args, **kwargs):
+        return round(function(*args, **kwargs))
+    return wrapper
+
+
+@rounded
+@doubled
+def price(base, tax):
+    return base * (1 + tax)
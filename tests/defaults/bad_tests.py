@@ -0,0 +1,3 @@
+from greeting import greet
+
+assert greet("Ada", "Hi", "?", shout=True) == "HI, ADA?"
@@ -0,0 +1,5 @@
+from greeting import greet
+
+assert greet("Ada") == "Hello, Ada!"
+assert greet("Ada", "Hi", "?") == "Hi, Ada?"
+assert greet("Ada", shout=True) == "HELLO, ADA!"
@@ -0,0 +1,7 @@
+def greet(name, greeting="Hello", punctuation=None, *, shout=False):
+    if punctuation is None:
+        punctuation = "!"
+    message = greeting + ", " + name + punctuation
+    if shout:
+        return message.upper()
+    return message
@@ -0,0 +1,6 @@
+from palette import mix, shades, split_pairs, unique
+
+mix([0, 2])
+split_pairs([1, (2, 3)])
+unique(["red"])
+shades([("red", ("dark", 1))])
@@ -0,0 +1,6 @@
+from palette import mix, shades, split_pairs, unique
+
+assert mix([0, 2]) == {"colors": ["red", "blue"], "count": 2}
+assert split_pairs([1, (2, 3)]) == [1, 2, 3]
+assert unique(["red", "red"]) == {"red", "white"}
+assert shades([("red", ("dark", 1))]) == ["red-dark"]
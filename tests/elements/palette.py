@@ -0,0 +1,20 @@
+PRIMARY = ("red", "green", "blue")
+
+
+def mix(colors):
+    names = [PRIMARY[index] for index in colors]
+    return {"colors": names, "count": len(names)}
+
+
+def split_pairs(pairs):
+    # Nested targets are not mutated
+    first, (second, third) = pairs[0], pairs[1]
+    return [first, second, third]
+
+
+def unique(colors):
+    return {color for color in colors} | {"white"}
+
+
+def shades(pairs):
+    return [name + "-" + shade for (name, (shade, _)) in pairs]
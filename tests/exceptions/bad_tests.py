@@ -0,0 +1,5 @@
+from parsing import parse_number, checked_ratio, release
+
+parse_number("12")
+checked_ratio(6, 3)
+release([])
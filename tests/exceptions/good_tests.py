@@ -0,0 +1,18 @@
+from parsing import log, parse_number, checked_ratio, release
+
+assert parse_number("12") == 12
+assert parse_number("x") is None
+assert log == ["parsed", "x", "parsed"]
+
+try:
+    parse_number(None)
+    assert False
+except TypeError:
+    pass
+
+assert checked_ratio(6, 3) == 2
+assert checked_ratio(1, 0) == 0
+
+resource = []
+release(resource)
+assert resource == ["used", "released"]
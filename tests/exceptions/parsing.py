@@ -0,0 +1,26 @@
+log = []
+
+
+def parse_number(text):
+    try:
+        return int(text)
+    except ValueError:
+        log.append(text)
+        return None
+    finally:
+        log.append("parsed")
+
+
+def checked_ratio(a, b):
+    try:
+        ratio = a / b
+    except ZeroDivisionError:
+        ratio = 0
+    return ratio
+
+
+def release(resource):
+    try:
+        resource.append("used")
+    finally:
+        resource.append("released")
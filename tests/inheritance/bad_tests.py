@@ -0,0 +1,4 @@
+from shapes import Shape, Square
+
+Shape().describe()
+Square(3)
@@ -0,0 +1,5 @@
+from shapes import Shape, Square
+
+assert Shape().area() == 0
+assert Square(3).area() == 9
+assert Square(2).describe() == "shape with area 4"
@@ -0,0 +1,18 @@
+class Shape:
+    def area(self):
+        return 0
+
+    def describe(self):
+        return "shape with area " + str(self.area())
+
+
+class Square(Shape):
+    def __init__(self, side):
+        self.side = side
+
+    def area(self):
+        return self.side * self.side
+
+
+class Point(object):
+    pass
@@ -0,0 +1,4 @@
+from search import first_negative, total_until_zero
+
+first_negative([1, -2, 3])
+total_until_zero([1, 2, 0, 5])
@@ -0,0 +1,7 @@
+from search import first_negative, total_until_zero
+
+assert first_negative([1, -2, 3]) == 1
+assert first_negative([1, 2]) == -1
+
+assert total_until_zero([1, 2, 0, 5]) == 3
+assert total_until_zero([1, 2]) == 6
@@ -0,0 +1,17 @@
+def first_negative(values):
+    for index, value in enumerate(values):
+        if value >= 0:
+            continue
+        return index
+    return -1
+
+
+def total_until_zero(values):
+    total = 0
+    for value in values:
+        if value == 0:
+            break
+        total += value
+    else:
+        total *= 2
+    return total
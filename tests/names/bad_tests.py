@@ -0,0 +1,5 @@
+from ledger import balance, label, safe_rate
+
+balance(10, [5, 7], 2)
+label(-3, "EUR")
+safe_rate(6, 3)
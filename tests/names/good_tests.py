@@ -0,0 +1,8 @@
+from ledger import balance, label, safe_rate
+
+assert balance(10, [5, 7], 2) == 20
+assert balance(0, [], 1) == -1
+assert label(-3, "EUR") == "-3 EUR"
+assert label(4, "USD") == "+4 USD"
+assert safe_rate(6, 3) == 2
+assert safe_rate(1, 0) == 0
@@ -0,0 +1,22 @@
+def balance(opening, deposits, fee):
+    total = opening
+    for amount in deposits:
+        total = total + amount
+    return total - fee
+
+
+def label(amount, currency):
+    if amount < 0:
+        sign = "-"
+    else:
+        sign = "+"
+    return sign + str(abs(amount)) + " " + currency
+
+
+def safe_rate(paid, owed):
+    try:
+        rate = paid / owed
+    except ZeroDivisionError as error:
+        # `error` is unbound after the handler, so it is never swapped in for `rate`
+        rate = 0
+    return rate
@@ -0,0 +1,5 @@
+from inventory import Inventory
+
+inventory = Inventory()
+inventory.add("apple", 2)
+inventory.remove("apple")
@@ -0,0 +1,15 @@
+from inventory import Inventory
+
+inventory = Inventory()
+inventory.add("apple", 2)
+inventory.add("apple", 3)
+assert inventory.items["apple"] == 5
+
+inventory.remove("apple")
+assert "apple" not in inventory.items
+
+try:
+    inventory.add("pear", 0)
+    assert False
+except ValueError:
+    pass
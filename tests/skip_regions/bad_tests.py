@@ -0,0 +1,3 @@
+from pricing import total
+
+total(3, 4)
@@ -0,0 +1,4 @@
+from pricing import total
+
+assert total(3, 4) == 12
+assert total(2, 0) == 0
@@ -0,0 +1,9 @@
+def log(message):
+    # pymut: off
+    prefix = "[pricing] "
+    print(prefix + message)
+    # pymut: on
+
+def total(price, quantity):
+    log("computing total")  # pymut: skip
+    return price * quantity
@@ -0,0 +1,5 @@
+from windows import middle, window, without_ends
+
+middle([1, 2, 3])
+window([1, 2, 3, 4, 5], 1, 2)
+without_ends([1, 2, 3, 4])
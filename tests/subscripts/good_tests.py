@@ -0,0 +1,7 @@
+from windows import middle, window, without_ends, lookup
+
+assert middle([1, 2, 3]) == 2
+assert middle([1, 2, 3, 4]) == 3
+assert window([1, 2, 3, 4, 5], 1, 2) == [2, 3]
+assert without_ends([1, 2, 3, 4]) == [2, 3]
+assert lookup({"scale": 2}, {(0, 1): 3, (1, 0): 5}, 0, 1) == 6
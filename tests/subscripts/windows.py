@@ -0,0 +1,16 @@
+def middle(values):
+    half = len(values) // 2
+    return values[half]
+
+
+def window(values, start, size):
+    return values[start:start + size]
+
+
+def without_ends(values):
+    return values[1:-1]
+
+
+def lookup(config, grid, row, column):
+    # String keys and tuple indices are not shifted
+    return config["scale"] * grid[row, column]
@@ -0,0 +1,149 @@
+"""Constructs whose printed form needs parentheses, escaping or special casing."""
+
+import os.path as path
+from .. import sibling
+from collections import OrderedDict, defaultdict as dd
+
+
+@decorator
+@decorator_factory(1, key="value")
+def signature(a, b: int = 1, *args, c, d=None, **kwargs) -> dict:
+    global counter
+    del args[0], kwargs["key"]
+    assert a, "message"
+    return {"a": a, **kwargs}
+
+
+def keyword_only(a, *, b=2):
+    def inner():
+        nonlocal a
+        a += 1
+        yield a
+        yield from range(a)
+        x = yield
+        return (yield a)
+    return inner
+
+
+class Child(Base, metaclass=Meta):
+    attribute: int = 0
+
+    async def method(self):
+        async with lock as (first, second):
+            async for item in await self.source():
+                await item
+
+
+def precedence(a, b, c):
+    values = [
+        (a + b) * c,
+        a + b * c,
+        a - (b - c),
+        (a - b) - c,
+        a ** b ** c,
+        (a ** b) ** c,
+        -a ** b,
+        (-a) ** b,
+        a ** -b,
+        (-1) ** a,
+        -(a + b),
+        not (a and b),
+        (not a) == b,
+        not a == b,
+        a or b and c,
+        (a or b) and c,
+        a if b else c,
+        (a if b else c) if a else b,
+        a if (b if c else a) else c,
+        lambda: a,
+        lambda x, y=1: x + y,
+        (lambda: a)(),
+        (a, b) if c else (),
+        a < b < c,
+        (a < b) < c,
+        a not in b,
+        a is not None,
+        ~a & b | c ^ a << 2 >> b,
+        (a | b) & c,
+        a @ b // c % a / b,
+        (1).real,
+        1.5.hex(),
+        a.b[c](a)[1:2],
+        a[1:2, ::3],
+        a[b:],
+        a[:-1],
+        a[::],
+        a[(b,)],
+        a[b, c],
+        {a, b},
+        {a: b, **c},
+        (a,),
+        [*a, *b],
+        f(*a, **b),
+        f(x for x in a),
+        [x for x in a if x if not x],
+        {x: y for x, y in a},
+        {x for x in a for y in x},
+        ...,
+        1e999,
+        2j,
+        0.1,
+        10 ** 20,
+    ]
+    return values
+
+
+def literals():
+    return [
+        'single',
+        "it's",
+        'say "hi"',
+        'both \' and "',
+        "tab\tnewline\nbackslash\\",
+        "unicode é and control \x01",
+        b"bytes\x00\xff'",
+        f"{literals} and {precedence!r}",
+        f"{precedence:>10}",
+        "{braces}" 'joined',
+        "",
+    ]
+
+
+def statements(a):
+    if a:
+        pass
+    elif not a:
+        pass
+    elif a is None:
+        pass
+    else:
+        if a:
+            pass
+
+    while a:
+        break
+    else:
+        pass
+
+    for x, y in a:
+        continue
+    else:
+        pass
+
+    try:
+        raise ValueError("message") from None
+    except (TypeError, ValueError) as error:
+        raise
+    except Exception:
+        pass
+    else:
+        pass
+    finally:
+        pass
+
+    with open(a) as handle, lock:
+        a = b = handle.read()
+        a, b = b, a
+        [a, b] = b, a
+        a[0] += 1
+        a.b -= 1
@@ -0,0 +1,19 @@
+#!/bin/sh
+
+# Every Python file of the tests must print back to a program with the same syntax tree, ignoring locations
+for file in ../*/*.py
+do
+    $PYMUT_PATH -m Unparse -f $file > printed.py
+    if python3 -c "import ast, sys; sys.exit(ast.dump(ast.parse(open(sys.argv[1]).read())) != ast.dump(ast.parse(open(sys.argv[2]).read())))" $file printed.py
+    then
+        echo "Round trip succeeded: $file"
+    else
+        echo "Round trip failed: $file"
+        diff $file printed.py
+    fi
+done
+rm -f printed.py
+
+rm -f mutations.db
+$PYMUT_PATH -m Explore -d mutations.db -f scaling.py
+sqlite3 mutations.db "select diff from mutations order by line, column;"